@@ -7,4 +7,74 @@ pub const SEED_SERVICE_OFFERING: &str = "service_offering";
 pub const SEED_SERVICE_OFFERING_GROUP: &str = "service_offering_group";
 
 #[constant]
-pub const SEED_LISTING: &str = "listing";
\ No newline at end of file
+pub const SEED_LISTING: &str = "listing";
+
+#[constant]
+pub const SEED_SUBSCRIPTION: &str = "subscription";
+
+#[constant]
+pub const SEED_MARKETPLACE_CONFIG: &str = "marketplace_config";
+
+#[constant]
+pub const SEED_ORDER_ESCROW: &str = "order_escrow";
+
+#[constant]
+pub const SEED_OFFER: &str = "offer";
+
+#[constant]
+pub const SEED_COLLECTION_BID: &str = "collection_bid";
+
+#[constant]
+pub const SEED_AUCTION: &str = "auction";
+
+#[constant]
+pub const SEED_PURCHASE_COUNTER: &str = "purchase_counter";
+
+#[constant]
+pub const SEED_COUPON: &str = "coupon";
+
+#[constant]
+pub const SEED_REFERRAL_STATS: &str = "referral_stats";
+
+#[constant]
+pub const SEED_REGISTERED_REFERRER: &str = "registered_referrer";
+
+// Lamports paid from the listing rent to whoever closes an expired listing
+#[constant]
+pub const EXPIRED_LISTING_BOUNTY: u64 = 100_000;
+
+// Upper bound for protocol fees on primary and secondary sales (10%)
+#[constant]
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+// Seconds the arbiter has to resolve a dispute before anyone can apply the default split (14 days)
+#[constant]
+pub const DISPUTE_TIMEOUT_SECS: i64 = 1_209_600;
+
+// Buyer share (in basis points) of a dispute left unresolved past the deadline
+#[constant]
+pub const DEFAULT_DISPUTE_BUYER_BPS: u16 = 5_000;
+
+// Maximum length of an evidence URI submitted in a dispute
+#[constant]
+pub const MAX_EVIDENCE_URI_LENGTH: usize = 200;
+
+// Asset attribute set to "true" once a service has been redeemed
+#[constant]
+pub const ATTRIBUTE_REDEEMED: &str = "redeemed";
+
+// Asset attribute holding the remaining uses of a multi-use service
+#[constant]
+pub const ATTRIBUTE_REMAINING_USES: &str = "remaining_uses";
+
+// Asset attribute holding the tier index of a tiered service
+#[constant]
+pub const ATTRIBUTE_TIER: &str = "tier";
+
+// Maximum number of tiers per service offering
+#[constant]
+pub const MAX_TIERS: usize = 5;
+
+// Maximum number of creators sharing the royalties of an offering
+#[constant]
+pub const MAX_CREATORS: usize = 5;
\ No newline at end of file
@@ -10,6 +10,150 @@ pub enum ServiceOfferingError {
 
     #[msg("Invalid OSS program")]
     InvalidOssProgram,
+
+    #[msg("Service offering has expired")]
+    ServiceExpired,
+
+    #[msg("Max quantity cannot be lower than the number sold")]
+    MaxQuantityBelowSold,
+
+    #[msg("Subscription period must be positive")]
+    InvalidSubscriptionPeriod,
+
+    #[msg("Subscription account is required for subscription offerings")]
+    MissingSubscription,
+
+    #[msg("Service is not a subscription")]
+    NotASubscription,
+
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+
+    #[msg("Token payment accounts are required for this offering")]
+    MissingPaymentAccounts,
+
+    #[msg("Escrow release timeout must be positive")]
+    InvalidEscrowTimeout,
+
+    #[msg("Escrowed offerings must be priced in SOL")]
+    EscrowRequiresSol,
+
+    #[msg("Order escrow account is required for escrowed offerings")]
+    MissingOrderEscrow,
+
+    #[msg("Service is not escrowed")]
+    NotEscrowed,
+
+    #[msg("Invalid order status")]
+    InvalidOrderStatus,
+
+    #[msg("Escrow release timeout has not passed")]
+    ReleaseTimeoutNotReached,
+
+    #[msg("An arbiter can only be set on escrowed offerings")]
+    ArbiterRequiresEscrow,
+
+    #[msg("Signer is not the buyer or vendor of the order")]
+    NotOrderParty,
+
+    #[msg("Order is not disputed")]
+    OrderNotDisputed,
+
+    #[msg("Signer is not the arbiter of the order")]
+    InvalidArbiter,
+
+    #[msg("Evidence URI is too long")]
+    EvidenceUriTooLong,
+
+    #[msg("Dispute split exceeds 100%")]
+    InvalidDisputeSplit,
+
+    #[msg("Asset does not belong to this service offering")]
+    InvalidAsset,
+
+    #[msg("Asset is locked")]
+    AssetIsLocked,
+
+    #[msg("Service has already been redeemed")]
+    AlreadyRedeemed,
+
+    #[msg("Uses per asset must be greater than zero")]
+    InvalidUsesPerAsset,
+
+    #[msg("Asset is not a multi-use service")]
+    NotMultiUse,
+
+    #[msg("No uses remaining")]
+    NoUsesRemaining,
+
+    #[msg("Buyer is not on the allowlist")]
+    NotAllowlisted,
+
+    #[msg("Allowlist allocation reached")]
+    AllowlistAllocationReached,
+
+    #[msg("Purchase counter is required for this purchase")]
+    MissingPurchaseCounter,
+
+    #[msg("Wallet has reached the purchase limit for this offering")]
+    WalletLimitReached,
+
+    #[msg("Sale has not started")]
+    SaleNotStarted,
+
+    #[msg("Sale has already started")]
+    SaleAlreadyStarted,
+
+    #[msg("Start time must be in the future and before the expiry")]
+    InvalidStartTime,
+
+    #[msg("Too many tiers")]
+    TooManyTiers,
+
+    #[msg("Invalid tier")]
+    InvalidTier,
+
+    #[msg("Tier sold out")]
+    TierSoldOut,
+
+    #[msg("Referral fee exceeds 100%")]
+    InvalidReferralBps,
+
+    #[msg("Referrals are only supported on offerings without escrow")]
+    UnsupportedReferral,
+
+    #[msg("Buyers can't refer themselves")]
+    InvalidReferrer,
+
+    #[msg("Referral stats are required when passing a referrer")]
+    MissingReferralStats,
+
+    #[msg("Creator shares must be unique creators summing to 100")]
+    InvalidCreatorShares,
+
+    #[msg("Too many creators")]
+    TooManyCreators,
+
+    #[msg("Referrer is not registered on this offering")]
+    UnregisteredReferrer,
+
+    #[msg("Referrer must hold at least the rent-exempt minimum")]
+    ReferrerNotRentExempt,
+
+    #[msg("The vendor can't arbitrate its own orders")]
+    VendorCannotArbitrate,
+
+    #[msg("Royalties plus the maximum protocol fee can't exceed 100%")]
+    InvalidRoyaltyBps,
+
+    #[msg("Offering is not priced with this price field")]
+    PriceNotUpdatable,
+
+    #[msg("Uses of the service have already been consumed")]
+    UsesAlreadyConsumed,
+
+    #[msg("Dispute deadline has not passed")]
+    DisputeDeadlineNotReached,
 }
 
 #[error_code]
@@ -28,4 +172,133 @@ pub enum ListingError {
 
     #[msg("Invalid group")]
     InvalidGroup,
-}
\ No newline at end of file
+
+    #[msg("Invalid creator")]
+    InvalidCreator,
+
+    #[msg("Invalid asset")]
+    InvalidAsset,
+
+    #[msg("Listing has not expired")]
+    ListingNotExpired,
+
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+
+    #[msg("Token payment accounts are required for this listing")]
+    MissingPaymentAccounts,
+
+    #[msg("Redeemed assets can't be listed")]
+    AssetIsRedeemed,
+
+    #[msg("Declining listings need an expiry and an end price below the start price")]
+    InvalidPricing,
+
+    #[msg("Invalid seller")]
+    InvalidSeller,
+
+    #[msg("Remaining accounts don't match the listings being bought")]
+    InvalidBatch,
+
+    #[msg("Total price exceeds the maximum")]
+    MaxTotalPriceExceeded,
+
+    #[msg("Price exceeds the maximum")]
+    MaxPriceExceeded,
+
+    #[msg("Expiry can only be extended or cleared")]
+    ExpiryNotExtended,
+
+    #[msg("Declining listings can't be updated, cancel and relist instead")]
+    DecliningListingLocked,
+}
+
+#[error_code]
+pub enum MarketplaceError {
+    #[msg("Protocol fee exceeds the maximum")]
+    FeeTooHigh,
+
+    #[msg("Signer is not the program upgrade authority")]
+    InvalidUpgradeAuthority,
+
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+}
+
+#[error_code]
+pub enum OfferError {
+    #[msg("Offer price must be greater than zero")]
+    InvalidPrice,
+
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Offer has expired")]
+    OfferExpired,
+
+    #[msg("Signer is not the owner of the asset")]
+    NotAssetOwner,
+
+    #[msg("Bid quantity must be greater than zero")]
+    InvalidQuantity,
+
+    #[msg("Asset has fewer remaining uses than the offer or bid requires")]
+    InsufficientUses,
+
+    #[msg("Asset is not of the tier the bid requires")]
+    TierMismatch,
+}
+
+#[error_code]
+pub enum AuctionError {
+    #[msg("Auction end must be in the future")]
+    InvalidEndTime,
+
+    #[msg("Extension must not be negative")]
+    InvalidExtension,
+
+    #[msg("Auction has ended")]
+    AuctionEnded,
+
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Bid is below the reserve or minimum increment")]
+    BidTooLow,
+
+    #[msg("Seller can't bid on their own auction")]
+    SellerCannotBid,
+
+    #[msg("Previous bidder doesn't match the highest bidder")]
+    InvalidPreviousBidder,
+
+    #[msg("Winner doesn't match the highest bidder")]
+    InvalidWinner,
+
+    #[msg("Minimum bid increment must be greater than zero")]
+    InvalidBidIncrement,
+}
+
+#[error_code]
+pub enum CouponError {
+    #[msg("Discount must be greater than zero and at most 100%")]
+    InvalidDiscount,
+
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Coupon has expired")]
+    CouponExpired,
+
+    #[msg("Coupon has no redemptions left")]
+    CouponExhausted,
+
+    #[msg("Invalid coupon code")]
+    InvalidCode,
+
+    #[msg("Coupon code is required to redeem the coupon")]
+    MissingCode,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ListingUpdated {
+    pub listing: Pubkey,
+    pub asset_id: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct ServiceRedeemed {
+    pub service_offering: Pubkey,
+    pub asset_id: Pubkey,
+    pub holder: Pubkey,
+    pub burned: bool,
+}
+
+#[event]
+pub struct UseConsumed {
+    pub service_offering: Pubkey,
+    pub asset_id: Pubkey,
+    pub remaining_uses: u64,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use nifty_asset::{accounts::Asset, types::State, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    calculate_royalties, get_attribute, get_remaining_uses, pay_escrowed_royalties, transfer_asset,
+    transfer_lamports, ListingError, MarketplaceConfig, MarketplaceError, Offer, OfferError,
+    ATTRIBUTE_REDEEMED, SEED_MARKETPLACE_CONFIG, SEED_OFFER,
+};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    // The current owner of the asset
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    // The bidder (receives the asset and the offer rent back)
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+
+    // The Asset being sold
+    /// CHECK: must match the asset stored on the offer, more checks in the handler
+    #[account(
+        mut,
+        address = offer.asset_id @ ListingError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub group_asset: UncheckedAccount<'info>,
+
+    // The offer PDA being accepted
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [
+            SEED_OFFER.as_bytes(),
+            asset.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require!(!offer.is_expired(), OfferError::OfferExpired);
+    require_keys_eq!(
+        asset.owner,
+        ctx.accounts.seller.key(),
+        OfferError::NotAssetOwner
+    );
+    require!(
+        asset.group.to_option() == Some(ctx.accounts.group_asset.key()),
+        ListingError::InvalidGroup
+    );
+    // Listed assets have to be cancelled before accepting an offer
+    require!(asset.state == State::Unlocked, ListingError::AssetIsLocked);
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ListingError::AssetIsRedeemed
+    );
+    // The bidder pays for the uses the asset had when the offer was made
+    if let Some(offered_uses) = offer.remaining_uses {
+        require!(
+            get_remaining_uses(&asset_info)?.is_some_and(|uses| uses >= offered_uses),
+            OfferError::InsufficientUses
+        );
+    }
+
+    // Process payment out of the offer escrow
+    let (royalty_shares, seller_amount) =
+        calculate_royalties(&ctx.accounts.group_asset.to_account_info(), offer.price)?;
+
+    let fee = ctx.accounts.marketplace_config.secondary_fee(offer.price)?;
+    let seller_amount = seller_amount
+        .checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let offer_info = offer.to_account_info();
+
+    // Creator accounts are passed as remaining accounts (unpayable dust shares go to the seller)
+    let withheld = pay_escrowed_royalties(&offer_info, ctx.remaining_accounts, &royalty_shares)?;
+    let seller_amount = seller_amount
+        .checked_add(withheld)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    transfer_lamports(&offer_info, &ctx.accounts.treasury.to_account_info(), fee)?;
+    transfer_lamports(
+        &offer_info,
+        &ctx.accounts.seller.to_account_info(),
+        seller_amount,
+    )?;
+
+    // Transfer the asset to the bidder
+    transfer_asset(
+        &asset_info,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.group_asset.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        &[],
+    )?;
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
-use crate::{Listing, ListingError, SEED_LISTING};
+use crate::{
+    calculate_royalties, pay_royalties, pay_token_royalties, transfer_tokens, Listing,
+    ListingError, MarketplaceConfig, MarketplaceError, RoyaltyShare, SEED_LISTING,
+    SEED_MARKETPLACE_CONFIG,
+};
 use anchor_lang::{
     prelude::*,
     solana_program::program::invoke_signed,
     system_program::{transfer, Transfer},
 };
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use nifty_asset::{
     accounts::Asset,
@@ -43,6 +48,57 @@ pub struct BuyListing<'info> {
     )]
     pub listing: Account<'info, Listing>,
 
+    // The SPL token mint the listing is paid in (only for token-priced listings)
+    #[account(
+        address = listing.payment_mint.unwrap_or_default() @ ListingError::InvalidPaymentMint
+    )]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The buyer's token account paying for the listing
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The seller's associated token account receiving the proceeds
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // SPL Token or Token-2022 program
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Box<Account<'info, MarketplaceConfig>>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    // The treasury's associated token account (only for token-priced listings)
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     /// CHECK: use address constraint
     #[account(
         address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
@@ -53,7 +109,111 @@ pub struct BuyListing<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<BuyListing>) -> Result<()> {
+impl<'info> BuyListing<'info> {
+    // Pays the seller, the protocol fee and creator royalties in SOL, or in the
+    // listing's payment mint when it has one
+    fn settle(
+        &self,
+        seller_amount: u64,
+        fee: u64,
+        royalty_shares: &[RoyaltyShare],
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        if self.listing.payment_mint.is_none() {
+            // Unpayable dust shares go to the seller
+            let withheld = pay_royalties(
+                &self.buyer.to_account_info(),
+                creator_accounts,
+                royalty_shares,
+                &self.system_program.to_account_info(),
+            )?;
+            let seller_amount = seller_amount
+                .checked_add(withheld)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            for (recipient, amount) in [
+                (&self.seller.to_account_info(), seller_amount),
+                (&self.treasury.to_account_info(), fee),
+            ] {
+                if amount == 0 {
+                    continue;
+                }
+
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.buyer.to_account_info(),
+                            to: recipient.clone(),
+                        },
+                    ),
+                    amount,
+                )?;
+            }
+
+            return Ok(());
+        }
+
+        let (
+            Some(mint),
+            Some(buyer_token_account),
+            Some(seller_token_account),
+            Some(token_program),
+        ) = (
+            &self.payment_mint,
+            &self.buyer_token_account,
+            &self.seller_token_account,
+            &self.token_program,
+        )
+        else {
+            return err!(ListingError::MissingPaymentAccounts);
+        };
+
+        transfer_tokens(
+            buyer_token_account,
+            seller_token_account,
+            &self.buyer.to_account_info(),
+            mint,
+            token_program,
+            seller_amount,
+            &[],
+        )?;
+
+        if fee > 0 {
+            let treasury_token_account = self
+                .treasury_token_account
+                .as_ref()
+                .ok_or(ListingError::MissingPaymentAccounts)?;
+
+            transfer_tokens(
+                buyer_token_account,
+                treasury_token_account,
+                &self.buyer.to_account_info(),
+                mint,
+                token_program,
+                fee,
+                &[],
+            )?;
+        }
+
+        pay_token_royalties(
+            &self.buyer.to_account_info(),
+            buyer_token_account,
+            creator_accounts,
+            royalty_shares,
+            mint,
+            token_program,
+            &[],
+        )
+    }
+}
+
+// Fails if the price charged exceeds `max_price`, so the seller can't reprice
+// the listing ahead of a pending purchase
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyListing<'info>>,
+    max_price: u64,
+) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     let asset_key = ctx.accounts.asset.key();
     let seller_key = ctx.accounts.seller.key();
@@ -65,47 +225,22 @@ pub fn handler(ctx: Context<BuyListing>) -> Result<()> {
         ListingError::InvalidGroup
     );
 
-    // Process payment
-    let _group_data = (*ctx.accounts.group_asset.data).borrow();
-    let royalty_basis_points: u64 = 100; // Placeholder for now
-
-    // TODO Deserialize the group data
+    // Process payment at the current price (declining listings drop over time)
+    let price = listing.current_price(Clock::get()?.unix_timestamp);
+    require!(price <= max_price, ListingError::MaxPriceExceeded);
+    let (royalty_shares, seller_amount) =
+        calculate_royalties(&ctx.accounts.group_asset.to_account_info(), price)?;
 
-    let price = listing.price;
-
-    // Calculate payment amount
-    let payment_amount = price
-        .checked_mul(royalty_basis_points)
-        .and_then(|product| product.checked_div(10_000))
+    let fee = ctx.accounts.marketplace_config.secondary_fee(price)?;
+    let seller_amount = seller_amount
+        .checked_sub(fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // Calculate royalty amount
-    let _royalty_amount = price
-        .checked_sub(payment_amount)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Creator accounts (or their token accounts) are passed as remaining accounts
+    ctx.accounts
+        .settle(seller_amount, fee, &royalty_shares, ctx.remaining_accounts)?;
 
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.seller.to_account_info(),
-            },
-        ),
-        payment_amount,
-    )?;
-
-    // Transfer royalties
-    /*     transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.royalties_receiver.to_account_info(),
-            },
-        ),
-        royalty_amount,
-    )?; */
+    let listing = &ctx.accounts.listing;
 
     // Unlock the asset
 
@@ -0,0 +1,189 @@
+use crate::{
+    calculate_royalties, pay_royalties, transfer_asset, Listing, ListingError, MarketplaceConfig,
+    MarketplaceError, SEED_LISTING, SEED_MARKETPLACE_CONFIG,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::program::invoke_signed,
+    system_program::{transfer, Transfer},
+};
+use nifty_asset::{accounts::Asset, instructions::UnlockBuilder, ID as NIFTY_ASSET_PROGRAM_ID};
+
+// Accounts passed per listing in the remaining accounts, before its creators
+const ACCOUNTS_PER_LISTING: usize = 4;
+
+#[derive(Accounts)]
+pub struct BuyListings<'info> {
+    // The buyer
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fees
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyListings<'info> {
+    fn pay(&self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.buyer.to_account_info(),
+                    to: recipient.clone(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+// Buys several SOL listings at once. Each listing is passed in the remaining
+// accounts as `listing, asset, seller, group_asset`, followed by the creators
+// of its group. Fails (and settles nothing) if the total exceeds `max_total_price`.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyListings<'info>>,
+    max_total_price: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut total_price: u64 = 0;
+
+    require!(!remaining_accounts.is_empty(), ListingError::InvalidBatch);
+
+    while !remaining_accounts.is_empty() {
+        require!(
+            remaining_accounts.len() >= ACCOUNTS_PER_LISTING,
+            ListingError::InvalidBatch
+        );
+        let (item_accounts, rest) = remaining_accounts.split_at(ACCOUNTS_PER_LISTING);
+        let [listing_info, asset_info, seller_info, group_info] = item_accounts else {
+            return err!(ListingError::InvalidBatch);
+        };
+
+        let listing = Account::<Listing>::try_from(listing_info)?;
+        let asset = Asset::try_from(asset_info)?;
+
+        require_keys_eq!(
+            listing.asset_id,
+            asset_info.key(),
+            ListingError::InvalidAsset
+        );
+        require_keys_eq!(
+            listing.seller,
+            seller_info.key(),
+            ListingError::InvalidSeller
+        );
+        require!(listing.is_active(), ListingError::ListingNotActive);
+        require!(
+            listing.payment_mint.is_none(),
+            ListingError::InvalidPaymentMint
+        );
+        require!(
+            asset.group.to_option() == Some(group_info.key()),
+            ListingError::InvalidGroup
+        );
+
+        let price = listing.current_price(now);
+        total_price = total_price
+            .checked_add(price)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        require!(
+            total_price <= max_total_price,
+            ListingError::MaxTotalPriceExceeded
+        );
+
+        // Process payment, the group's creators follow the listing accounts
+        let (royalty_shares, seller_amount) = calculate_royalties(group_info, price)?;
+        require!(
+            rest.len() >= royalty_shares.len(),
+            ListingError::InvalidCreator
+        );
+        let (creator_accounts, rest) = rest.split_at(royalty_shares.len());
+
+        let fee = ctx.accounts.marketplace_config.secondary_fee(price)?;
+        let seller_amount = seller_amount
+            .checked_sub(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Unpayable dust shares go to the seller
+        let withheld = pay_royalties(
+            &ctx.accounts.buyer.to_account_info(),
+            creator_accounts,
+            &royalty_shares,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let seller_amount = seller_amount
+            .checked_add(withheld)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        ctx.accounts.pay(seller_info, seller_amount)?;
+        ctx.accounts
+            .pay(&ctx.accounts.treasury.to_account_info(), fee)?;
+
+        // Unlock the asset and transfer it to the buyer
+        let asset_key = asset_info.key();
+        let seller_key = seller_info.key();
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            SEED_LISTING.as_bytes(),
+            asset_key.as_ref(),
+            seller_key.as_ref(),
+            &[listing.bump],
+        ]];
+
+        let unlock_ix = UnlockBuilder::new()
+            .asset(asset_key)
+            .signer(listing_info.key())
+            .instruction();
+
+        invoke_signed(
+            &unlock_ix,
+            &[
+                asset_info.clone(),
+                listing_info.clone(),
+                ctx.accounts.oss_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        transfer_asset(
+            asset_info,
+            listing_info,
+            &ctx.accounts.buyer.to_account_info(),
+            group_info,
+            &ctx.accounts.oss_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        // Close the listing back to the seller
+        listing.close(seller_info.clone())?;
+
+        remaining_accounts = rest;
+    }
+
+    Ok(())
+}
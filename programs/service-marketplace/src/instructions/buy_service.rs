@@ -1,10 +1,33 @@
 use anchor_lang::system_program::{transfer, Transfer};
-use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hashv, program::invoke_signed},
+};
 
 use crate::{
-    ServiceOffering, ServiceOfferingError, SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP,
+    allowlist_leaf, transfer_tokens, verify_merkle_proof, Coupon, CouponError, MarketplaceConfig,
+    MarketplaceError, OrderEscrow, PurchaseCounter, ReferralStats, RegisteredReferrer,
+    ServiceOffering,
+    ServiceOfferingError, ServiceType, Subscription, ATTRIBUTE_REMAINING_USES, ATTRIBUTE_TIER,
+    SEED_MARKETPLACE_CONFIG, SEED_ORDER_ESCROW, SEED_PURCHASE_COUNTER, SEED_REFERRAL_STATS,
+    SEED_REGISTERED_REFERRER,
+    SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP, SEED_SUBSCRIPTION,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use nifty_asset::{
+    extensions::{AttributesBuilder, ExtensionBuilder},
+    instructions::CreateBuilder,
+    types::{ExtensionInput, ExtensionType, Standard},
+    ID as NIFTY_ASSET_PROGRAM_ID,
 };
-use nifty_asset::{instructions::CreateBuilder, types::Standard, ID as NIFTY_ASSET_PROGRAM_ID};
+
+// Proof that the buyer is on the offering's allowlist, with their allocation
+// (0 for no per-address limit)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AllowlistProof {
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
 
 #[derive(Accounts)]
 #[instruction(offering_name: String)]
@@ -45,6 +68,143 @@ pub struct BuyService<'info> {
     #[account(mut)]
     pub new_asset: Signer<'info>,
 
+    // Subscription PDA for the new asset (only for subscription offerings)
+    #[account(
+        init,
+        payer = buyer,
+        space = Subscription::get_size(),
+        seeds = [
+            SEED_SUBSCRIPTION.as_bytes(),
+            new_asset.key().as_ref(),
+        ],
+        bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+
+    // Order escrow PDA for the new asset (only for escrowed offerings)
+    #[account(
+        init,
+        payer = buyer,
+        space = OrderEscrow::get_size(),
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            new_asset.key().as_ref(),
+        ],
+        bump
+    )]
+    pub order_escrow: Option<Box<Account<'info, OrderEscrow>>>,
+
+    // Purchases made by the buyer on this offering (required for allowlist
+    // allocations and per-wallet limits)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PurchaseCounter::get_size(),
+        seeds = [
+            SEED_PURCHASE_COUNTER.as_bytes(),
+            service_offering.key().as_ref(),
+            buyer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub purchase_counter: Option<Box<Account<'info, PurchaseCounter>>>,
+
+    // The referrer of the purchase, paid `referral_bps` of the vendor proceeds
+    // (in SOL, or to its token account for token-priced offerings)
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
+    // The vendor's registration of the referrer (required with a referrer)
+    #[account(
+        seeds = [
+            SEED_REGISTERED_REFERRER.as_bytes(),
+            service_offering.key().as_ref(),
+            referrer.as_ref().map(|referrer| referrer.key()).unwrap_or_default().as_ref(),
+        ],
+        bump = registered_referrer.bump
+    )]
+    pub registered_referrer: Option<Box<Account<'info, RegisteredReferrer>>>,
+
+    // Cumulative stats of the referrer in the offering's currency (required with a referrer)
+    #[account(
+        mut,
+        seeds = [
+            SEED_REFERRAL_STATS.as_bytes(),
+            referrer.as_ref().map(|referrer| referrer.key()).unwrap_or_default().as_ref(),
+            service_offering.payment_mint.unwrap_or_default().as_ref(),
+        ],
+        bump = referral_stats.bump
+    )]
+    pub referral_stats: Option<Box<Account<'info, ReferralStats>>>,
+
+    // Coupon applied to the purchase (redeemed with its secret code)
+    #[account(
+        mut,
+        has_one = service_offering
+    )]
+    pub coupon: Option<Box<Account<'info, Coupon>>>,
+
+    // The SPL token mint the offering is paid in (only for token-priced offerings)
+    #[account(
+        address = service_offering.payment_mint.unwrap_or_default() @ ServiceOfferingError::InvalidPaymentMint
+    )]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The buyer's token account paying for the service
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The vendor's associated token account receiving the payment
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = vendor,
+        associated_token::token_program = token_program,
+    )]
+    pub vendor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The referrer's associated token account receiving the referral cut
+    // (only for referred purchases of token-priced offerings)
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = referrer,
+        associated_token::token_program = token_program,
+    )]
+    pub referrer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // SPL Token or Token-2022 program
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Box<Account<'info, MarketplaceConfig>>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    // The treasury's associated token account (only for token-priced offerings)
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     /// CHECK: use address constraint
     #[account(
         address = NIFTY_ASSET_PROGRAM_ID @ ServiceOfferingError::InvalidOssProgram
@@ -55,7 +215,192 @@ pub struct BuyService<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<BuyService>, offering_name: String) -> Result<()> {
+impl<'info> BuyService<'info> {
+    // Checks the buyer against the offering's allowlist, and their allocation
+    // against the purchases made so far
+    fn check_allowlist(&self, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        let Some(root) = self.service_offering.allowlist_root else {
+            return Ok(());
+        };
+
+        let AllowlistProof { allocation, proof } =
+            allowlist_proof.ok_or(ServiceOfferingError::NotAllowlisted)?;
+        require!(
+            verify_merkle_proof(&proof, root, allowlist_leaf(&self.buyer.key(), allocation)),
+            ServiceOfferingError::NotAllowlisted
+        );
+
+        if allocation > 0 {
+            let purchase_counter = self
+                .purchase_counter
+                .as_ref()
+                .ok_or(ServiceOfferingError::MissingPurchaseCounter)?;
+            require!(
+                purchase_counter.purchased < allocation,
+                ServiceOfferingError::AllowlistAllocationReached
+            );
+        }
+
+        Ok(())
+    }
+
+    // Checks the purchases made so far by the buyer against the offering's per-wallet limit
+    fn check_wallet_limit(&self) -> Result<()> {
+        if self.service_offering.max_per_wallet == 0 {
+            return Ok(());
+        }
+
+        let purchase_counter = self
+            .purchase_counter
+            .as_ref()
+            .ok_or(ServiceOfferingError::MissingPurchaseCounter)?;
+        require!(
+            self.service_offering
+                .is_within_wallet_limit(purchase_counter.purchased),
+            ServiceOfferingError::WalletLimitReached
+        );
+
+        Ok(())
+    }
+
+    // Redeems the coupon, if one is passed, and returns the discounted price
+    fn apply_coupon(&mut self, price: u64, coupon_code: Option<String>) -> Result<u64> {
+        let Some(coupon) = self.coupon.as_mut() else {
+            return Ok(price);
+        };
+
+        let code = coupon_code.ok_or(CouponError::MissingCode)?;
+        coupon.redeem(hashv(&[code.as_bytes()]).to_bytes())?;
+        coupon.discounted_price(price)
+    }
+
+    // Pays the protocol fee to the treasury, the referrer's cut of the vendor
+    // proceeds, and the rest of the price to the vendor. Returns the referral amount.
+    fn settle(&self, price: u64) -> Result<u64> {
+        let fee = self.marketplace_config.primary_fee(price)?;
+        let vendor_amount = price
+            .checked_sub(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let referral_amount = match &self.referrer {
+            Some(referrer) => {
+                // Only referrers registered by the vendor earn payouts
+                require!(
+                    self.registered_referrer.is_some(),
+                    ServiceOfferingError::UnregisteredReferrer
+                );
+                // A SOL transfer can't leave the referrer below the rent-exempt minimum
+                if self.service_offering.payment_mint.is_none() {
+                    require!(
+                        referrer.lamports() >= Rent::get()?.minimum_balance(0),
+                        ServiceOfferingError::ReferrerNotRentExempt
+                    );
+                }
+
+                let amount = self.service_offering.referral_amount(vendor_amount)?;
+                self.pay(
+                    &referrer.to_account_info(),
+                    self.referrer_token_account.as_deref(),
+                    amount,
+                )?;
+                amount
+            }
+            None => 0,
+        };
+        let vendor_amount = vendor_amount
+            .checked_sub(referral_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.pay(
+            &self.treasury.to_account_info(),
+            self.treasury_token_account.as_deref(),
+            fee,
+        )?;
+        self.pay(
+            &self.vendor.to_account_info(),
+            self.vendor_token_account.as_deref(),
+            vendor_amount,
+        )?;
+
+        Ok(referral_amount)
+    }
+
+    // Adds the purchase to the referrer's stats
+    fn record_referral(&mut self, amount: u64) -> Result<()> {
+        let Some(referrer) = &self.referrer else {
+            require!(
+                self.referral_stats.is_none() && self.registered_referrer.is_none(),
+                ServiceOfferingError::InvalidReferrer
+            );
+            return Ok(());
+        };
+        require_keys_neq!(
+            referrer.key(),
+            self.buyer.key(),
+            ServiceOfferingError::InvalidReferrer
+        );
+
+        let referral_stats = self
+            .referral_stats
+            .as_mut()
+            .ok_or(ServiceOfferingError::MissingReferralStats)?;
+        referral_stats.record_referral(amount)
+    }
+
+    // Pays from the buyer in SOL, or in the offering's payment mint when it has one
+    fn pay(
+        &self,
+        recipient: &AccountInfo<'info>,
+        recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.service_offering.payment_mint.is_none() {
+            return transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.buyer.to_account_info(),
+                        to: recipient.clone(),
+                    },
+                ),
+                amount,
+            );
+        }
+
+        match (
+            &self.payment_mint,
+            &self.buyer_token_account,
+            recipient_token_account,
+            &self.token_program,
+        ) {
+            (Some(mint), Some(from), Some(to), Some(token_program)) => transfer_tokens(
+                from,
+                to,
+                &self.buyer.to_account_info(),
+                mint,
+                token_program,
+                amount,
+                &[],
+            ),
+            _ => err!(ServiceOfferingError::MissingPaymentAccounts),
+        }
+    }
+}
+
+pub(crate) fn handler(
+    ctx: Context<BuyService>,
+    offering_name: String,
+    allowlist_proof: Option<AllowlistProof>,
+    tier: Option<u8>,
+    coupon_code: Option<String>,
+) -> Result<()> {
+    ctx.accounts.check_allowlist(allowlist_proof)?;
+    ctx.accounts.check_wallet_limit()?;
+
     let service_offering = &mut ctx.accounts.service_offering;
     let vendor_key = ctx.accounts.vendor.key();
     let service_offering_key = service_offering.key();
@@ -88,24 +433,112 @@ pub fn handler(ctx: Context<BuyService>, offering_name: String) -> Result<()> {
         combined_signer_seeds,
         offering_name.clone(),
         service_offering.is_transferrable,
+        asset_attributes(service_offering, tier),
     )?;
 
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.vendor.to_account_info(),
-            },
-        ),
-        service_offering.sol_price,
-    )?;
+    let price = ctx.accounts.service_offering.tier_price(tier)?;
+    let price = ctx.accounts.apply_coupon(price, coupon_code)?;
 
-    service_offering.increment_sold()?;
+    match ctx.accounts.service_offering.escrow_timeout_secs {
+        Some(release_timeout_secs) => {
+            // Escrowed proceeds aren't paid at purchase time, so there is no referral cut
+            require!(
+                ctx.accounts.referrer.is_none()
+                    && ctx.accounts.registered_referrer.is_none()
+                    && ctx.accounts.referral_stats.is_none()
+                    && ctx.accounts.referrer_token_account.is_none(),
+                ServiceOfferingError::UnsupportedReferral
+            );
+            let fee = ctx.accounts.marketplace_config.primary_fee(price)?;
+            let arbiter = ctx
+                .accounts
+                .service_offering
+                .arbiter
+                .unwrap_or(ctx.accounts.marketplace_config.arbiter);
+            let coupon = ctx.accounts.coupon.as_ref().map(|coupon| coupon.key());
+            let purchase_counted = ctx.accounts.purchase_counter.is_some();
+            let order_escrow = ctx
+                .accounts
+                .order_escrow
+                .as_mut()
+                .ok_or(ServiceOfferingError::MissingOrderEscrow)?;
+
+            // Hold the full price until the order is released or refunded
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: order_escrow.to_account_info(),
+                    },
+                ),
+                price,
+            )?;
+
+            order_escrow.create(
+                ctx.accounts.buyer.key(),
+                vendor_key,
+                service_offering_key,
+                ctx.accounts.new_asset.key(),
+                price,
+                fee,
+                release_timeout_secs,
+                arbiter,
+                coupon,
+                purchase_counted,
+                ctx.bumps.order_escrow.unwrap(),
+            );
+        }
+        None => {
+            require!(
+                ctx.accounts.order_escrow.is_none(),
+                ServiceOfferingError::NotEscrowed
+            );
+            let referral_amount = ctx.accounts.settle(price)?;
+            ctx.accounts.record_referral(referral_amount)?;
+        }
+    }
+
+    let service_offering = &mut ctx.accounts.service_offering;
+
+    match service_offering.service_type {
+        ServiceType::Subscription { period_secs, .. } => {
+            let subscription = ctx
+                .accounts
+                .subscription
+                .as_mut()
+                .ok_or(ServiceOfferingError::MissingSubscription)?;
+
+            subscription.create(
+                service_offering_key,
+                ctx.accounts.new_asset.key(),
+                period_secs,
+                tier,
+                ctx.bumps.subscription.unwrap(),
+            )?;
+        }
+        ServiceType::OneTime => {
+            require!(
+                ctx.accounts.subscription.is_none(),
+                ServiceOfferingError::NotASubscription
+            );
+        }
+    }
+
+    service_offering.increment_sold(tier)?;
+
+    if let Some(purchase_counter) = ctx.accounts.purchase_counter.as_mut() {
+        purchase_counter.record_purchase(
+            service_offering_key,
+            ctx.accounts.buyer.key(),
+            ctx.bumps.purchase_counter.unwrap(),
+        )?;
+    }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create_asset(
     asset_key: &Pubkey,
     payer_key: &Pubkey,
@@ -116,6 +549,7 @@ fn create_asset(
     signer_seeds: &[&[&[u8]]; 1],
     asset_name: String,
     is_transferrable: bool,
+    attributes: Option<Vec<u8>>,
 ) -> Result<()> {
     let standard = if is_transferrable {
         Standard::NonFungible
@@ -123,6 +557,16 @@ fn create_asset(
         Standard::Soulbound
     };
 
+    let extensions = attributes
+        .map(|data| {
+            vec![ExtensionInput {
+                extension_type: ExtensionType::Attributes,
+                length: data.len() as u32,
+                data: Some(data),
+            }]
+        })
+        .unwrap_or_default();
+
     let create_ix = CreateBuilder::new()
         .asset(*asset_key)
         .authority(*authority_key, true)
@@ -133,9 +577,26 @@ fn create_asset(
         .name(asset_name)
         .standard(standard)
         .mutable(true)
+        .extensions(extensions)
         .instruction();
 
     invoke_signed(&create_ix, account_infos, signer_seeds)?;
 
     Ok(())
 }
+
+// Attributes written on the new asset at mint (the remaining uses counter and the tier)
+fn asset_attributes(service_offering: &ServiceOffering, tier: Option<u8>) -> Option<Vec<u8>> {
+    if service_offering.uses_per_asset.is_none() && tier.is_none() {
+        return None;
+    }
+
+    let mut attributes_builder = AttributesBuilder::default();
+    if let Some(uses) = service_offering.uses_per_asset {
+        attributes_builder.add(ATTRIBUTE_REMAINING_USES, &uses.to_string());
+    }
+    if let Some(tier) = tier {
+        attributes_builder.add(ATTRIBUTE_TIER, &tier.to_string());
+    }
+    Some(attributes_builder.data())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollectionBid, SEED_COLLECTION_BID};
+
+#[derive(Accounts)]
+pub struct CancelCollectionBid<'info> {
+    // The bidder (receives the escrowed amount and rent back)
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // The collection bid PDA being closed
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [
+            SEED_COLLECTION_BID.as_bytes(),
+            collection_bid.group_asset.as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = collection_bid.bump
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+}
+
+pub(crate) fn handler(_ctx: Context<CancelCollectionBid>) -> Result<()> {
+    // Closing the bid returns the escrow for the unfilled quantity with the rent
+    Ok(())
+}
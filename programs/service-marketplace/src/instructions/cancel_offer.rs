@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{Offer, SEED_OFFER};
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    // The bidder (receives the escrowed price and rent back)
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // The offer PDA being closed
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [
+            SEED_OFFER.as_bytes(),
+            offer.asset_id.as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+}
+
+pub(crate) fn handler(_ctx: Context<CancelOffer>) -> Result<()> {
+    // Closing the offer returns the escrowed price with the rent
+    Ok(())
+}
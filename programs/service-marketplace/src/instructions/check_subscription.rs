@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use nifty_asset::accounts::Asset;
+
+use crate::{Subscription, SEED_SUBSCRIPTION};
+
+#[derive(Accounts)]
+pub struct CheckSubscription<'info> {
+    // The holder being checked
+    /// CHECK: only compared against the asset owner
+    pub holder: UncheckedAccount<'info>,
+
+    // The subscription Asset
+    /// CHECK: must match the asset stored on the subscription
+    #[account(address = subscription.asset_id)]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SUBSCRIPTION.as_bytes(),
+            asset.key().as_ref(),
+        ],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+}
+
+// Returns true when the holder owns the asset and its subscription is paid through now
+pub(crate) fn handler(ctx: Context<CheckSubscription>) -> Result<bool> {
+    let asset: Asset = Asset::try_from(&ctx.accounts.asset.to_account_info())?;
+
+    Ok(asset.owner == ctx.accounts.holder.key() && ctx.accounts.subscription.is_current())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{Coupon, ServiceOffering, SEED_COUPON, SEED_SERVICE_OFFERING};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct CloseCoupon<'info> {
+    // The vendor that owns the service offering (receives the coupon rent back)
+    #[account(mut)]
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // The coupon PDA being closed
+    #[account(
+        mut,
+        close = vendor,
+        has_one = service_offering,
+        seeds = [
+            SEED_COUPON.as_bytes(),
+            service_offering.key().as_ref(),
+            coupon.code_hash.as_ref(),
+        ],
+        bump = coupon.bump
+    )]
+    pub coupon: Account<'info, Coupon>,
+}
+
+pub(crate) fn handler(_ctx: Context<CloseCoupon>, _offering_name: String) -> Result<()> {
+    // Closing the coupon stops further redemptions and returns the rent
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::{unlock_and_revoke, Listing, ListingError, EXPIRED_LISTING_BOUNTY, SEED_LISTING};
+use anchor_lang::prelude::*;
+use nifty_asset::ID as NIFTY_ASSET_PROGRAM_ID;
+
+#[derive(Accounts)]
+pub struct CloseExpiredListing<'info> {
+    // Anyone can crank an expired listing (receives the bounty)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    // The seller (receives the listing rent back)
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    // The listed Asset
+    /// CHECK: must match the asset stored on the listing
+    #[account(
+        mut,
+        address = listing.asset_id @ ListingError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    // The expired listing PDA being closed
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [
+            SEED_LISTING.as_bytes(),
+            asset.key().as_ref(),
+            seller.key().as_ref(),
+        ],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseExpiredListing>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    let asset_key = ctx.accounts.asset.key();
+    let seller_key = ctx.accounts.seller.key();
+
+    require!(listing.is_expired(), ListingError::ListingNotExpired);
+
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        SEED_LISTING.as_bytes(),
+        asset_key.as_ref(),
+        seller_key.as_ref(),
+        &[listing.bump],
+    ]];
+
+    unlock_and_revoke(
+        &ctx.accounts.asset.to_account_info(),
+        &listing.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    // Pay the cranker's bounty out of the listing rent, the rest goes back to the seller on close
+    let listing_info = listing.to_account_info();
+    let bounty = EXPIRED_LISTING_BOUNTY.min(listing_info.lamports());
+
+    if bounty > 0 && ctx.accounts.cranker.key() != seller_key {
+        **listing_info.try_borrow_mut_lamports()? -= bounty;
+        **ctx.accounts.cranker.to_account_info().try_borrow_mut_lamports()? += bounty;
+    }
+
+    Ok(())
+}
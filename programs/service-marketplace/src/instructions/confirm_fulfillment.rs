@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    transfer_lamports, MarketplaceConfig, MarketplaceError, OrderEscrow, OrderStatus,
+    ServiceOfferingError, SEED_MARKETPLACE_CONFIG, SEED_ORDER_ESCROW,
+};
+
+#[derive(Accounts)]
+pub struct ConfirmFulfillment<'info> {
+    // The buyer confirming delivery (receives the escrow rent back)
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    // The vendor receiving the escrowed payment
+    #[account(mut)]
+    pub vendor: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        has_one = buyer,
+        has_one = vendor
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+
+    // Global marketplace config (treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ConfirmFulfillment>) -> Result<()> {
+    let order_escrow = &ctx.accounts.order_escrow;
+
+    // The buyer can confirm delivery even if the vendor never marked it
+    require!(
+        matches!(
+            order_escrow.status,
+            OrderStatus::Paid | OrderStatus::Fulfilled
+        ),
+        ServiceOfferingError::InvalidOrderStatus
+    );
+
+    release_to_vendor(
+        order_escrow,
+        &ctx.accounts.vendor.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+    )
+}
+
+// Pays the escrowed amount to the vendor and the protocol fee to the treasury.
+// The escrow rent is returned to the buyer when the account is closed.
+pub fn release_to_vendor<'info>(
+    order_escrow: &Account<'info, OrderEscrow>,
+    vendor: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+) -> Result<()> {
+    let escrow_info = order_escrow.to_account_info();
+
+    transfer_lamports(&escrow_info, treasury, order_escrow.fee)?;
+    transfer_lamports(&escrow_info, vendor, order_escrow.vendor_amount()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use nifty_asset::{accounts::Asset, types::State, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    get_attribute, get_remaining_uses, set_attribute, ServiceOffering, ServiceOfferingError,
+    UseConsumed, ATTRIBUTE_REDEEMED, ATTRIBUTE_REMAINING_USES, SEED_SERVICE_OFFERING,
+};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct ConsumeUse<'info> {
+    // The vendor delivering one use of the service (pays for the attribute storage)
+    #[account(mut)]
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // The multi-use Asset
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ServiceOfferingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<ConsumeUse>, offering_name: String) -> Result<()> {
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require!(
+        asset.group.to_option() == Some(ctx.accounts.service_offering.asset_id),
+        ServiceOfferingError::InvalidAsset
+    );
+    // Listed assets are locked, so the count shown on a listing can't change under the buyer
+    require!(
+        asset.state == State::Unlocked,
+        ServiceOfferingError::AssetIsLocked
+    );
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ServiceOfferingError::AlreadyRedeemed
+    );
+
+    let remaining_uses = get_remaining_uses(&asset_info)?
+        .ok_or(ServiceOfferingError::NotMultiUse)?
+        .checked_sub(1)
+        .ok_or(ServiceOfferingError::NoUsesRemaining)?;
+
+    let vendor_key = ctx.accounts.vendor.key();
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        SEED_SERVICE_OFFERING.as_bytes(),
+        vendor_key.as_ref(),
+        offering_name.as_bytes(),
+        &[ctx.accounts.service_offering.bump],
+    ]];
+
+    set_attribute(
+        &asset_info,
+        &ctx.accounts.service_offering.to_account_info(),
+        &ctx.accounts.vendor.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        ATTRIBUTE_REMAINING_USES,
+        &remaining_uses.to_string(),
+        signer_seeds,
+    )?;
+
+    emit!(UseConsumed {
+        service_offering: ctx.accounts.service_offering.key(),
+        asset_id: asset_info.key(),
+        remaining_uses,
+    });
+
+    Ok(())
+}
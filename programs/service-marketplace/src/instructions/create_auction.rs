@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use nifty_asset::{
+    accounts::Asset,
+    types::{Standard, State},
+    ID as NIFTY_ASSET_PROGRAM_ID,
+};
+
+use crate::{
+    approve_and_lock, get_attribute, Auction, AuctionError, ListingError, ATTRIBUTE_REDEEMED,
+    SEED_AUCTION,
+};
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    // The seller
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    // The Asset being auctioned
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    // New PDA for the auction
+    #[account(
+        init,
+        space = Auction::get_size(),
+        payer = seller,
+        seeds = [
+            SEED_AUCTION.as_bytes(),
+            asset.key().as_ref(),
+            seller.key().as_ref(),
+        ],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<CreateAuction>,
+    reserve_price: u64,
+    min_bid_increment: u64,
+    ends_at: i64,
+    extension_secs: i64,
+) -> Result<()> {
+    let asset_key = ctx.accounts.asset.key();
+    let seller_key = ctx.accounts.seller.key();
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require!(
+        ends_at > Clock::get()?.unix_timestamp,
+        AuctionError::InvalidEndTime
+    );
+    require!(extension_secs >= 0, AuctionError::InvalidExtension);
+    // Equal bids would otherwise take the lead and extend the auction forever
+    require!(min_bid_increment > 0, AuctionError::InvalidBidIncrement);
+    require!(
+        asset.standard != Standard::Soulbound,
+        ListingError::AssetIsSoulbound
+    );
+    require!(asset.state == State::Unlocked, ListingError::AssetIsLocked);
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ListingError::AssetIsRedeemed
+    );
+    require_keys_eq!(asset.owner, seller_key);
+
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        SEED_AUCTION.as_bytes(),
+        asset_key.as_ref(),
+        seller_key.as_ref(),
+        &[ctx.bumps.auction],
+    ]];
+
+    // Same escrow-in-place as listings: the auction PDA holds the Transfer+Lock delegation
+    approve_and_lock(
+        &asset_info,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.auction.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    ctx.accounts.auction.create(
+        seller_key,
+        asset_key,
+        reserve_price,
+        min_bid_increment,
+        ends_at,
+        extension_secs,
+        ctx.bumps.auction,
+    );
+
+    Ok(())
+}
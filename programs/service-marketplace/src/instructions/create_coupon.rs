@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{Coupon, CouponError, Discount, ServiceOffering, SEED_COUPON, SEED_SERVICE_OFFERING};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String, code_hash: [u8; 32])]
+pub struct CreateCoupon<'info> {
+    // The vendor that owns the service offering (pays the coupon rent)
+    #[account(mut)]
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // New PDA for the coupon
+    #[account(
+        init,
+        space = Coupon::get_size(),
+        payer = vendor,
+        seeds = [
+            SEED_COUPON.as_bytes(),
+            service_offering.key().as_ref(),
+            code_hash.as_ref(),
+        ],
+        bump
+    )]
+    pub coupon: Account<'info, Coupon>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<CreateCoupon>,
+    _offering_name: String,
+    code_hash: [u8; 32],
+    discount: Discount,
+    max_redemptions: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    match discount {
+        Discount::Percentage { bps } => {
+            require!(bps > 0 && bps <= 10_000, CouponError::InvalidDiscount)
+        }
+        Discount::Fixed { amount } => require!(amount > 0, CouponError::InvalidDiscount),
+    }
+    if let Some(expiry) = expires_at {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            CouponError::InvalidExpiry
+        );
+    }
+
+    ctx.accounts.coupon.create(
+        ctx.accounts.service_offering.key(),
+        code_hash,
+        discount,
+        max_redemptions,
+        expires_at,
+        ctx.bumps.coupon,
+    );
+
+    Ok(())
+}
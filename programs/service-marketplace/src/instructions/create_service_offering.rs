@@ -4,7 +4,9 @@ use anchor_lang::{
 };
 
 use crate::{
-    ServiceOffering, ServiceOfferingError, SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP,
+    build_tiers, validate_creator_shares, validate_starts_at, CreatorShare, RedeemMode,
+    ServiceOffering, ServiceOfferingError, ServiceType, TierConfig, MAX_PROTOCOL_FEE_BPS,
+    SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP,
 };
 
 use nifty_asset::{
@@ -60,7 +62,8 @@ pub struct CreateServiceOffering<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handler(
     ctx: Context<CreateServiceOffering>,
     offering_name: String,
     max_quantity: u64,
@@ -73,7 +76,74 @@ pub fn handler(
     royalty_basis_points: u64,
     terms_of_service_uri: String,
     is_transferrable: bool,
+    service_type: ServiceType,
+    payment_mint: Option<Pubkey>,
+    token_price: u64,
+    escrow_timeout_secs: Option<i64>,
+    arbiter: Option<Pubkey>,
+    redeem_mode: RedeemMode,
+    uses_per_asset: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+    max_per_wallet: u64,
+    starts_at: Option<i64>,
+    tiers: Vec<TierConfig>,
+    referral_bps: u16,
+    creators: Vec<CreatorShare>,
 ) -> Result<()> {
+    if let ServiceType::Subscription { period_secs, .. } = service_type {
+        require!(period_secs > 0, ServiceOfferingError::InvalidSubscriptionPeriod);
+    }
+
+    if let Some(timeout) = escrow_timeout_secs {
+        require!(timeout > 0, ServiceOfferingError::InvalidEscrowTimeout);
+        require!(payment_mint.is_none(), ServiceOfferingError::EscrowRequiresSol);
+    }
+
+    // Disputes only exist for escrowed orders
+    require!(
+        arbiter.is_none() || escrow_timeout_secs.is_some(),
+        ServiceOfferingError::ArbiterRequiresEscrow
+    );
+    require!(
+        arbiter != Some(ctx.accounts.vendor.key()),
+        ServiceOfferingError::VendorCannotArbitrate
+    );
+
+    validate_starts_at(starts_at, expires_at)?;
+
+    // Royalties go to the vendor alone unless a split is given
+    let creators = if creators.is_empty() {
+        vec![CreatorShare {
+            address: ctx.accounts.vendor.key(),
+            share: 100,
+        }]
+    } else {
+        creators
+    };
+    validate_creator_shares(&creators)?;
+
+    // Secondary sales pay royalties and the protocol fee out of the same price
+    require!(
+        royalty_basis_points <= 10_000 - MAX_PROTOCOL_FEE_BPS as u64,
+        ServiceOfferingError::InvalidRoyaltyBps
+    );
+
+    // Referrers are paid out of the vendor proceeds at purchase time
+    require!(
+        referral_bps <= 10_000,
+        ServiceOfferingError::InvalidReferralBps
+    );
+    require!(
+        referral_bps == 0 || escrow_timeout_secs.is_none(),
+        ServiceOfferingError::UnsupportedReferral
+    );
+    let tiers = build_tiers(tiers)?;
+
+    require!(
+        uses_per_asset != Some(0),
+        ServiceOfferingError::InvalidUsesPerAsset
+    );
+
     let service_offering = &mut ctx.accounts.service_offering;
     let vendor_key = ctx.accounts.vendor.key();
     let service_offering_key = service_offering.key();
@@ -82,9 +152,21 @@ pub fn handler(
         vendor_key,
         ctx.accounts.offering_group_asset.key(),
         max_quantity,
+        max_per_wallet,
         sol_price,
+        payment_mint,
+        token_price,
         expires_at,
+        starts_at,
         is_transferrable,
+        service_type,
+        escrow_timeout_secs,
+        arbiter,
+        redeem_mode,
+        uses_per_asset,
+        tiers,
+        referral_bps,
+        allowlist_root,
         ctx.bumps.service_offering,
     );
 
@@ -129,6 +211,7 @@ pub fn handler(
         &account_infos,
         combined_signer_seeds,
         royalty_basis_points,
+        &creators,
     )?;
 
     add_terms_of_service(
@@ -161,6 +244,7 @@ pub fn handler(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_metadata(
     symbol: String,
     description: String,
@@ -226,6 +310,7 @@ fn add_royalties(
     account_infos: &[AccountInfo],
     signer_seeds: &[&[&[u8]]; 2],
     basis_points: u64,
+    creator_shares: &[CreatorShare],
 ) -> Result<()> {
     // Part 1: Define the royalty amounts
 
@@ -249,7 +334,14 @@ fn add_royalties(
     // Part 2: Define the creators
 
     let mut creators = CreatorsBuilder::default();
-    creators.add(&payer_key, true, 100); // for now, limit to the creator
+    for creator in creator_shares {
+        // Only the vendor signs, so only the vendor is marked verified
+        creators.add(
+            &creator.address,
+            creator.address == *payer_key,
+            creator.share,
+        );
+    }
 
     let creators_data = creators.data();
 
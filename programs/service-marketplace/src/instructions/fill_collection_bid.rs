@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use nifty_asset::{accounts::Asset, types::State, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    calculate_royalties, get_attribute, get_remaining_uses, get_tier, pay_escrowed_royalties,
+    transfer_asset, transfer_lamports, CollectionBid, ListingError, MarketplaceConfig,
+    MarketplaceError, OfferError, ATTRIBUTE_REDEEMED, SEED_COLLECTION_BID, SEED_MARKETPLACE_CONFIG,
+};
+
+#[derive(Accounts)]
+pub struct FillCollectionBid<'info> {
+    // The current owner of the asset
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    // The bidder (receives the asset, and the bid rent once it is filled)
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+
+    // The Asset being sold
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    // The offering group asset the bid is made on
+    /// CHECK: must match the group stored on the bid
+    #[account(
+        mut,
+        address = collection_bid.group_asset @ ListingError::InvalidGroup
+    )]
+    pub group_asset: UncheckedAccount<'info>,
+
+    // The collection bid PDA being filled
+    #[account(
+        mut,
+        has_one = bidder,
+        seeds = [
+            SEED_COLLECTION_BID.as_bytes(),
+            group_asset.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = collection_bid.bump
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FillCollectionBid<'info>>) -> Result<()> {
+    let collection_bid = &ctx.accounts.collection_bid;
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require!(!collection_bid.is_expired(), OfferError::OfferExpired);
+    require_keys_eq!(
+        asset.owner,
+        ctx.accounts.seller.key(),
+        OfferError::NotAssetOwner
+    );
+    // Any asset of the group can fill the bid
+    require!(
+        asset.group.to_option() == Some(ctx.accounts.group_asset.key()),
+        ListingError::InvalidGroup
+    );
+    require!(asset.state == State::Unlocked, ListingError::AssetIsLocked);
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ListingError::AssetIsRedeemed
+    );
+    // An unredeemed single-use asset has its one use left
+    let remaining_uses = get_remaining_uses(&asset_info)?.unwrap_or(1);
+    require!(
+        remaining_uses > 0 && remaining_uses >= collection_bid.min_remaining_uses,
+        OfferError::InsufficientUses
+    );
+    if let Some(tier) = collection_bid.tier {
+        require!(
+            get_tier(&asset_info)? == Some(tier),
+            OfferError::TierMismatch
+        );
+    }
+
+    // Process payment for one unit out of the bid escrow
+    let (royalty_shares, seller_amount) = calculate_royalties(
+        &ctx.accounts.group_asset.to_account_info(),
+        collection_bid.price,
+    )?;
+
+    let fee = ctx
+        .accounts
+        .marketplace_config
+        .secondary_fee(collection_bid.price)?;
+    let seller_amount = seller_amount
+        .checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let bid_info = collection_bid.to_account_info();
+
+    // Creator accounts are passed as remaining accounts (unpayable dust shares go to the seller)
+    let withheld = pay_escrowed_royalties(&bid_info, ctx.remaining_accounts, &royalty_shares)?;
+    let seller_amount = seller_amount
+        .checked_add(withheld)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    transfer_lamports(&bid_info, &ctx.accounts.treasury.to_account_info(), fee)?;
+    transfer_lamports(
+        &bid_info,
+        &ctx.accounts.seller.to_account_info(),
+        seller_amount,
+    )?;
+
+    // Transfer the asset to the bidder
+    transfer_asset(
+        &asset_info,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.group_asset.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        &[],
+    )?;
+
+    let collection_bid = &mut ctx.accounts.collection_bid;
+    collection_bid.fill();
+
+    // The last fill closes the bid and returns the rent to the bidder
+    if collection_bid.is_filled() {
+        collection_bid.close(ctx.accounts.bidder.to_account_info())?;
+    }
+
+    Ok(())
+}
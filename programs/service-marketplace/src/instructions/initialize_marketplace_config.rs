@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    program::ServiceMarketplace, MarketplaceConfig, MarketplaceError, SEED_MARKETPLACE_CONFIG,
+};
+
+#[derive(Accounts)]
+pub struct InitializeMarketplaceConfig<'info> {
+    // The program upgrade authority (pays for the config)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = MarketplaceConfig::get_size(),
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+    )]
+    pub program: Program<'info, ServiceMarketplace>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ MarketplaceError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<InitializeMarketplaceConfig>,
+    admin: Pubkey,
+    treasury: Pubkey,
+    arbiter: Pubkey,
+    primary_fee_bps: u16,
+    secondary_fee_bps: u16,
+) -> Result<()> {
+    let marketplace_config = &mut ctx.accounts.marketplace_config;
+
+    marketplace_config.bump = ctx.bumps.marketplace_config;
+    marketplace_config.update(admin, treasury, arbiter, primary_fee_bps, secondary_fee_bps)
+}
@@ -1,10 +1,12 @@
-use crate::{Listing, ListingError, SEED_LISTING};
-use anchor_lang::{
-    prelude::*,
-    solana_program::program::{invoke, invoke_signed},
+use crate::{
+    approve_and_lock, get_attribute, get_remaining_uses, Listing, ListingError, ListingPricing,
+    ATTRIBUTE_REDEEMED, SEED_LISTING,
 };
+use anchor_lang::prelude::*;
 use nifty_asset::{
-    accounts::Asset, instructions::{ApproveBuilder, LockBuilder}, types::{DelegateInput, DelegateRole, Standard, State}, ID as NIFTY_ASSET_PROGRAM_ID
+    accounts::Asset,
+    types::{Standard, State},
+    ID as NIFTY_ASSET_PROGRAM_ID,
 };
 
 #[derive(Accounts)]
@@ -42,7 +44,13 @@ pub struct ListAsset<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ListAsset>, price: u64, expires_at: Option<i64>) -> Result<()> {
+pub(crate) fn handler(
+    ctx: Context<ListAsset>,
+    price: u64,
+    expires_at: Option<i64>,
+    payment_mint: Option<Pubkey>,
+    pricing: ListingPricing,
+) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     let asset_key = ctx.accounts.asset.key();
     let seller_key = ctx.accounts.seller.key();
@@ -56,27 +64,13 @@ pub fn handler(ctx: Context<ListAsset>, price: u64, expires_at: Option<i64>) ->
         ListingError::AssetIsSoulbound
     );
     require!(asset.state == State::Unlocked, ListingError::AssetIsLocked);
+    require!(
+        get_attribute(&ctx.accounts.asset.to_account_info(), ATTRIBUTE_REDEEMED)?.is_none(),
+        ListingError::AssetIsRedeemed
+    );
+    let remaining_uses = get_remaining_uses(&ctx.accounts.asset.to_account_info())?;
     require_keys_eq!(owner, ctx.accounts.seller.key());
 
-    let approve_ix = ApproveBuilder::new()
-        .asset(ctx.accounts.asset.key())
-        .owner(ctx.accounts.seller.key())
-        .delegate(listing.key())
-        .delegate_input(DelegateInput::Some {
-            roles: vec![DelegateRole::Transfer, DelegateRole::Lock],
-        })
-        .instruction();
-
-    invoke(
-        &approve_ix,
-        &[
-            ctx.accounts.asset.to_account_info(),
-            ctx.accounts.seller.to_account_info(),
-            listing.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
-
     let signer_seeds: &[&[&[u8]]; 1] = &[&[
         SEED_LISTING.as_bytes(),
         asset_key.as_ref(),
@@ -84,25 +78,26 @@ pub fn handler(ctx: Context<ListAsset>, price: u64, expires_at: Option<i64>) ->
         &[ctx.bumps.listing],
     ]];
 
-    let account_infos = vec![
-        ctx.accounts.asset.to_account_info(),
-        listing.to_account_info(),
-        ctx.accounts.oss_program.to_account_info(),
-    ];
-
-    let lock_ix = LockBuilder::new()
-        .asset(ctx.accounts.asset.key())
-        .signer(listing.key())
-        .instruction();
-    invoke_signed(&lock_ix, &account_infos, signer_seeds)?;
+    approve_and_lock(
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &listing.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        signer_seeds,
+    )?;
 
     listing.create(
         ctx.accounts.seller.key(),
         ctx.accounts.asset.key(),
         price,
+        pricing,
+        payment_mint,
         expires_at,
+        remaining_uses,
         ctx.bumps.listing,
     );
+    listing.validate_pricing()?;
 
     Ok(())
 }
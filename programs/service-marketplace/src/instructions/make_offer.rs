@@ -0,0 +1,85 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use nifty_asset::{accounts::Asset, types::Standard, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    get_attribute, get_remaining_uses, ListingError, Offer, OfferError, ATTRIBUTE_REDEEMED,
+    SEED_OFFER,
+};
+
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    // The bidder (escrows the offered price)
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // The Asset the offer is made on
+    /// CHECK: must be owned by the Nifty program, more checks in the handler
+    #[account(owner = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidAsset)]
+    pub asset: UncheckedAccount<'info>,
+
+    // New PDA for the offer
+    #[account(
+        init,
+        space = Offer::get_size(),
+        payer = bidder,
+        seeds = [
+            SEED_OFFER.as_bytes(),
+            asset.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<MakeOffer>, price: u64, expires_at: Option<i64>) -> Result<()> {
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require!(price > 0, OfferError::InvalidPrice);
+    if let Some(expiry) = expires_at {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            OfferError::InvalidExpiry
+        );
+    }
+    require!(
+        asset.standard != Standard::Soulbound,
+        ListingError::AssetIsSoulbound
+    );
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ListingError::AssetIsRedeemed
+    );
+    // Recorded so the offer can't be accepted after uses are consumed
+    let remaining_uses = get_remaining_uses(&asset_info)?;
+
+    // The offered price stays in the offer PDA until it is accepted or cancelled
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.offer.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    ctx.accounts.offer.create(
+        ctx.accounts.bidder.key(),
+        asset_info.key(),
+        price,
+        expires_at,
+        remaining_uses,
+        ctx.bumps.offer,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{ServiceOffering, ServiceOfferingError, SEED_SERVICE_OFFERING};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct ManageServiceOffering<'info> {
+    // The vendor that owns the service offering
+    pub vendor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+}
+
+impl ManageServiceOffering<'_> {
+    fn validate(&self) -> Result<()> {
+        require!(
+            !self.service_offering.is_expired(),
+            ServiceOfferingError::ServiceExpired
+        );
+        Ok(())
+    }
+}
+
+pub fn activate_handler(ctx: Context<ManageServiceOffering>, _offering_name: String) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts.service_offering.activate();
+    Ok(())
+}
+
+pub fn deactivate_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts.service_offering.deactivate();
+    Ok(())
+}
+
+pub fn update_sol_price_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    sol_price: u64,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts.service_offering.update_sol_price(sol_price)
+}
+
+pub fn update_token_price_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    token_price: u64,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts
+        .service_offering
+        .update_token_price(token_price)
+}
+
+pub fn update_tier_price_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    tier: u8,
+    price: u64,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts.service_offering.update_tier_price(tier, price)
+}
+
+pub fn update_max_quantity_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    max_quantity: u64,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts
+        .service_offering
+        .update_max_quantity(max_quantity)
+}
+
+pub fn update_allowlist_root_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts
+        .service_offering
+        .update_allowlist_root(allowlist_root);
+    Ok(())
+}
+
+pub fn reschedule_handler(
+    ctx: Context<ManageServiceOffering>,
+    _offering_name: String,
+    starts_at: Option<i64>,
+) -> Result<()> {
+    ctx.accounts.validate()?;
+    ctx.accounts.service_offering.reschedule(starts_at)
+}
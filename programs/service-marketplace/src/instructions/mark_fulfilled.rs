@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{OrderEscrow, OrderStatus, ServiceOfferingError, SEED_ORDER_ESCROW};
+
+#[derive(Accounts)]
+pub struct MarkFulfilled<'info> {
+    // The vendor that delivered the service
+    pub vendor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        has_one = vendor
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+}
+
+pub(crate) fn handler(ctx: Context<MarkFulfilled>) -> Result<()> {
+    let order_escrow = &mut ctx.accounts.order_escrow;
+
+    require!(
+        order_escrow.status == OrderStatus::Paid,
+        ServiceOfferingError::InvalidOrderStatus
+    );
+
+    order_escrow.mark_fulfilled();
+
+    Ok(())
+}
@@ -2,8 +2,72 @@ pub mod create_service_offering;
 pub mod buy_service;
 pub mod list_asset;
 pub mod buy_listing;
+pub mod buy_listings;
+pub mod cancel_listing;
+pub mod close_expired_listing;
+pub mod update_listing;
+pub mod manage_service_offering;
+pub mod renew_subscription;
+pub mod check_subscription;
+pub mod initialize_marketplace_config;
+pub mod update_marketplace_config;
+pub mod mark_fulfilled;
+pub mod confirm_fulfillment;
+pub mod release_escrow;
+pub mod request_refund;
+pub mod open_dispute;
+pub mod submit_evidence_uri;
+pub mod resolve_dispute;
+pub mod resolve_expired_dispute;
+pub mod redeem_service;
+pub mod consume_use;
+pub mod make_offer;
+pub mod cancel_offer;
+pub mod accept_offer;
+pub mod place_collection_bid;
+pub mod cancel_collection_bid;
+pub mod fill_collection_bid;
+pub mod create_auction;
+pub mod place_bid;
+pub mod settle_auction;
+pub mod create_coupon;
+pub mod close_coupon;
+pub mod register_referrer;
+pub mod revoke_referrer;
 
 pub use create_service_offering::*;
 pub use buy_service::*;
 pub use list_asset::*;
-pub use buy_listing::*;
\ No newline at end of file
+pub use buy_listing::*;
+pub use buy_listings::*;
+pub use cancel_listing::*;
+pub use close_expired_listing::*;
+pub use update_listing::*;
+pub use manage_service_offering::*;
+pub use renew_subscription::*;
+pub use check_subscription::*;
+pub use initialize_marketplace_config::*;
+pub use update_marketplace_config::*;
+pub use mark_fulfilled::*;
+pub use confirm_fulfillment::*;
+pub use release_escrow::*;
+pub use request_refund::*;
+pub use open_dispute::*;
+pub use submit_evidence_uri::*;
+pub use resolve_dispute::*;
+pub use resolve_expired_dispute::*;
+pub use redeem_service::*;
+pub use consume_use::*;
+pub use make_offer::*;
+pub use cancel_offer::*;
+pub use accept_offer::*;
+pub use place_collection_bid::*;
+pub use cancel_collection_bid::*;
+pub use fill_collection_bid::*;
+pub use create_auction::*;
+pub use place_bid::*;
+pub use settle_auction::*;
+pub use create_coupon::*;
+pub use close_coupon::*;
+pub use register_referrer::*;
+pub use revoke_referrer::*;
\ No newline at end of file
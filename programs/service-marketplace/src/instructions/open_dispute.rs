@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{OrderEscrow, ServiceOfferingError, SEED_ORDER_ESCROW};
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    // The buyer or vendor of the order
+    pub party: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        constraint = order_escrow.is_party(&party.key()) @ ServiceOfferingError::NotOrderParty
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenDispute>) -> Result<()> {
+    // Freezes the escrow: only the arbiter can move the funds from here,
+    // until the dispute deadline passes and the default split applies
+    ctx.accounts.order_escrow.open_dispute()
+}
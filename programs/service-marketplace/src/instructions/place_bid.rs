@@ -0,0 +1,82 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{transfer_lamports, Auction, AuctionError, SEED_AUCTION};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    // The bidder (escrows the bid)
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_AUCTION.as_bytes(),
+            auction.asset_id.as_ref(),
+            auction.seller.as_ref(),
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    // The previous highest bidder (refunded when outbid)
+    /// CHECK: must match the highest bidder stored on the auction
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    require_keys_neq!(
+        ctx.accounts.bidder.key(),
+        auction.seller,
+        AuctionError::SellerCannotBid
+    );
+
+    let previous_bid = auction
+        .highest_bidder
+        .map(|bidder| (bidder, auction.highest_bid));
+
+    auction.place_bid(ctx.accounts.bidder.key(), amount)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: auction.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Refund the bidder that was outbid
+    if let Some((previous_bidder_key, previous_amount)) = previous_bid {
+        let previous_bidder = ctx
+            .accounts
+            .previous_bidder
+            .as_ref()
+            .ok_or(AuctionError::InvalidPreviousBidder)?;
+
+        require_keys_eq!(
+            previous_bidder.key(),
+            previous_bidder_key,
+            AuctionError::InvalidPreviousBidder
+        );
+
+        transfer_lamports(
+            &auction.to_account_info(),
+            &previous_bidder.to_account_info(),
+            previous_amount,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use nifty_asset::ID as NIFTY_ASSET_PROGRAM_ID;
+
+use crate::{CollectionBid, ListingError, OfferError, SEED_COLLECTION_BID};
+
+#[derive(Accounts)]
+pub struct PlaceCollectionBid<'info> {
+    // The bidder (escrows price * quantity)
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // The offering group asset the bid is made on
+    /// CHECK: must be owned by the Nifty program
+    #[account(owner = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidGroup)]
+    pub group_asset: UncheckedAccount<'info>,
+
+    // New PDA for the collection bid
+    #[account(
+        init,
+        space = CollectionBid::get_size(),
+        payer = bidder,
+        seeds = [
+            SEED_COLLECTION_BID.as_bytes(),
+            group_asset.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PlaceCollectionBid>,
+    price: u64,
+    quantity: u64,
+    expires_at: Option<i64>,
+    min_remaining_uses: u64,
+    tier: Option<u8>,
+) -> Result<()> {
+    require!(price > 0, OfferError::InvalidPrice);
+    require!(quantity > 0, OfferError::InvalidQuantity);
+    if let Some(expiry) = expires_at {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            OfferError::InvalidExpiry
+        );
+    }
+
+    let collection_bid = &mut ctx.accounts.collection_bid;
+
+    collection_bid.create(
+        ctx.accounts.bidder.key(),
+        ctx.accounts.group_asset.key(),
+        price,
+        quantity,
+        expires_at,
+        min_remaining_uses,
+        tier,
+        ctx.bumps.collection_bid,
+    );
+
+    // The price of every unit stays in the bid PDA until it is filled or cancelled
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: collection_bid.to_account_info(),
+            },
+        ),
+        collection_bid.escrow_amount()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use nifty_asset::{accounts::Asset, types::State, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    burn_asset, get_attribute, set_attribute, RedeemMode, ServiceOffering, ServiceOfferingError,
+    ServiceRedeemed, ATTRIBUTE_REDEEMED, SEED_SERVICE_OFFERING,
+};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct RedeemService<'info> {
+    // The holder of the service asset (pays for the attribute storage)
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    // The vendor confirming the service was delivered
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // The Asset being redeemed
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    // The offering group asset
+    /// CHECK: must match the group stored on the service offering
+    #[account(
+        mut,
+        address = service_offering.asset_id @ ServiceOfferingError::InvalidAsset
+    )]
+    pub offering_group_asset: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ServiceOfferingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<RedeemService>, offering_name: String) -> Result<()> {
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let asset = Asset::try_from(&asset_info)?;
+
+    require_keys_eq!(asset.owner, ctx.accounts.holder.key());
+    require!(
+        asset.group.to_option() == Some(ctx.accounts.service_offering.asset_id),
+        ServiceOfferingError::InvalidAsset
+    );
+    // Listed assets are locked by the listing and have to be cancelled first
+    require!(asset.state == State::Unlocked, ServiceOfferingError::AssetIsLocked);
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ServiceOfferingError::AlreadyRedeemed
+    );
+
+    let redeem_mode = ctx.accounts.service_offering.redeem_mode;
+
+    match redeem_mode {
+        RedeemMode::Attribute => {
+            let vendor_key = ctx.accounts.vendor.key();
+            let signer_seeds: &[&[&[u8]]; 1] = &[&[
+                SEED_SERVICE_OFFERING.as_bytes(),
+                vendor_key.as_ref(),
+                offering_name.as_bytes(),
+                &[ctx.accounts.service_offering.bump],
+            ]];
+
+            set_attribute(
+                &asset_info,
+                &ctx.accounts.service_offering.to_account_info(),
+                &ctx.accounts.holder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.oss_program.to_account_info(),
+                ATTRIBUTE_REDEEMED,
+                "true",
+                signer_seeds,
+            )?;
+        }
+        RedeemMode::Burn => {
+            burn_asset(
+                &asset_info,
+                &ctx.accounts.holder.to_account_info(),
+                &ctx.accounts.offering_group_asset.to_account_info(),
+                &ctx.accounts.oss_program.to_account_info(),
+            )?;
+        }
+    }
+
+    emit!(ServiceRedeemed {
+        service_offering: ctx.accounts.service_offering.key(),
+        asset_id: asset_info.key(),
+        holder: ctx.accounts.holder.key(),
+        burned: redeem_mode == RedeemMode::Burn,
+    });
+
+    Ok(())
+}
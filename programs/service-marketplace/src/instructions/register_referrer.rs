@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    ReferralStats, RegisteredReferrer, ServiceOffering, SEED_REFERRAL_STATS,
+    SEED_REGISTERED_REFERRER, SEED_SERVICE_OFFERING,
+};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String, referrer: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    // The vendor that owns the service offering (pays the registration and stats rent)
+    #[account(mut)]
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // New PDA allowing the referrer to earn payouts on the offering
+    #[account(
+        init,
+        space = RegisteredReferrer::get_size(),
+        payer = vendor,
+        seeds = [
+            SEED_REGISTERED_REFERRER.as_bytes(),
+            service_offering.key().as_ref(),
+            referrer.as_ref(),
+        ],
+        bump
+    )]
+    pub registered_referrer: Account<'info, RegisteredReferrer>,
+
+    // Cumulative stats of the referrer in the offering's currency (created on first registration)
+    #[account(
+        init_if_needed,
+        space = ReferralStats::get_size(),
+        payer = vendor,
+        seeds = [
+            SEED_REFERRAL_STATS.as_bytes(),
+            referrer.as_ref(),
+            service_offering.payment_mint.unwrap_or_default().as_ref(),
+        ],
+        bump
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RegisterReferrer>,
+    _offering_name: String,
+    referrer: Pubkey,
+) -> Result<()> {
+    ctx.accounts.registered_referrer.create(
+        ctx.accounts.service_offering.key(),
+        referrer,
+        ctx.bumps.registered_referrer,
+    );
+    ctx.accounts.referral_stats.init(
+        referrer,
+        ctx.accounts.service_offering.payment_mint,
+        ctx.bumps.referral_stats,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    release_to_vendor, MarketplaceConfig, MarketplaceError, OrderEscrow, ServiceOfferingError,
+    SEED_MARKETPLACE_CONFIG, SEED_ORDER_ESCROW,
+};
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    // Anyone can crank a timed out escrow
+    pub cranker: Signer<'info>,
+
+    // The buyer (receives the escrow rent back)
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    // The vendor receiving the escrowed payment
+    #[account(mut)]
+    pub vendor: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        has_one = buyer,
+        has_one = vendor
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+
+    // Global marketplace config (treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ReleaseEscrow>) -> Result<()> {
+    let order_escrow = &ctx.accounts.order_escrow;
+
+    require!(
+        order_escrow.is_release_timed_out(),
+        ServiceOfferingError::ReleaseTimeoutNotReached
+    );
+
+    release_to_vendor(
+        order_escrow,
+        &ctx.accounts.vendor.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+    )
+}
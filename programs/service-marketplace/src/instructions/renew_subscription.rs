@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    transfer_tokens, MarketplaceConfig, MarketplaceError, ServiceOffering, ServiceOfferingError,
+    ServiceType, Subscription, SEED_MARKETPLACE_CONFIG, SEED_SERVICE_OFFERING, SEED_SUBSCRIPTION,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct RenewSubscription<'info> {
+    // Whoever pays for the renewal (typically the holder)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // The public key of the vendor offering the service (receiving the payment)
+    #[account(mut)]
+    pub vendor: SystemAccount<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SUBSCRIPTION.as_bytes(),
+            subscription.asset_id.as_ref(),
+        ],
+        bump = subscription.bump,
+        has_one = service_offering
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    // The SPL token mint the offering is paid in (only for token-priced offerings)
+    #[account(
+        address = service_offering.payment_mint.unwrap_or_default() @ ServiceOfferingError::InvalidPaymentMint
+    )]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The payer's token account paying for the renewal
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The vendor's associated token account receiving the payment
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = vendor,
+        associated_token::token_program = token_program,
+    )]
+    pub vendor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // SPL Token or Token-2022 program
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Box<Account<'info, MarketplaceConfig>>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    // The treasury's associated token account (only for token-priced offerings)
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Solana program system account
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RenewSubscription<'info> {
+    // Pays the protocol fee to the treasury and the rest of the price to the vendor
+    fn settle(&self, price: u64) -> Result<()> {
+        let fee = self.marketplace_config.primary_fee(price)?;
+        let vendor_amount = price
+            .checked_sub(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.pay(
+            &self.treasury.to_account_info(),
+            self.treasury_token_account.as_deref(),
+            fee,
+        )?;
+        self.pay(
+            &self.vendor.to_account_info(),
+            self.vendor_token_account.as_deref(),
+            vendor_amount,
+        )
+    }
+
+    // Pays from the payer in SOL, or in the offering's payment mint when it has one
+    fn pay(
+        &self,
+        recipient: &AccountInfo<'info>,
+        recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.service_offering.payment_mint.is_none() {
+            return transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: recipient.clone(),
+                    },
+                ),
+                amount,
+            );
+        }
+
+        match (
+            &self.payment_mint,
+            &self.payer_token_account,
+            recipient_token_account,
+            &self.token_program,
+        ) {
+            (Some(mint), Some(from), Some(to), Some(token_program)) => transfer_tokens(
+                from,
+                to,
+                &self.payer.to_account_info(),
+                mint,
+                token_program,
+                amount,
+                &[],
+            ),
+            _ => err!(ServiceOfferingError::MissingPaymentAccounts),
+        }
+    }
+}
+
+pub(crate) fn handler(ctx: Context<RenewSubscription>, _offering_name: String) -> Result<()> {
+    let service_offering = &ctx.accounts.service_offering;
+
+    let period_secs = match service_offering.service_type {
+        ServiceType::Subscription { period_secs, .. } => period_secs,
+        ServiceType::OneTime => return err!(ServiceOfferingError::NotASubscription),
+    };
+    let renewal_price = service_offering.renewal_price(ctx.accounts.subscription.tier)?;
+
+    require!(
+        service_offering.accepts_renewals(),
+        ServiceOfferingError::ServiceNotActive
+    );
+
+    ctx.accounts.settle(renewal_price)?;
+
+    ctx.accounts.subscription.renew(period_secs)?;
+
+    Ok(())
+}
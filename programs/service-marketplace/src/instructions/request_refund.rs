@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use nifty_asset::ID as NIFTY_ASSET_PROGRAM_ID;
+
+use crate::{
+    burn_asset, get_attribute, get_remaining_uses, get_tier, Coupon, OrderEscrow, OrderStatus,
+    PurchaseCounter, ServiceOffering, ServiceOfferingError, ATTRIBUTE_REDEEMED, SEED_ORDER_ESCROW,
+    SEED_PURCHASE_COUNTER,
+};
+
+#[derive(Accounts)]
+pub struct RequestRefund<'info> {
+    // The buyer (receives the escrowed payment and rent back)
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        has_one = buyer,
+        has_one = service_offering
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+
+    #[account(mut)]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // The Asset minted for the order (burned on refund)
+    /// CHECK: must match the asset stored on the order escrow
+    #[account(
+        mut,
+        address = order_escrow.asset_id
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    // The offering group asset
+    /// CHECK: must match the group stored on the service offering
+    #[account(
+        mut,
+        address = service_offering.asset_id
+    )]
+    pub offering_group_asset: UncheckedAccount<'info>,
+
+    // The buyer's purchase counter (required when the order was counted on it)
+    #[account(
+        mut,
+        seeds = [
+            SEED_PURCHASE_COUNTER.as_bytes(),
+            service_offering.key().as_ref(),
+            buyer.key().as_ref(),
+        ],
+        bump = purchase_counter.bump
+    )]
+    pub purchase_counter: Option<Box<Account<'info, PurchaseCounter>>>,
+
+    // The coupon redeemed for the order, unless the vendor has closed it since
+    #[account(
+        mut,
+        address = order_escrow.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<Box<Account<'info, Coupon>>>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ServiceOfferingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RequestRefund>) -> Result<()> {
+    // Refunds are only possible before the vendor delivers
+    require!(
+        ctx.accounts.order_escrow.status == OrderStatus::Paid,
+        ServiceOfferingError::InvalidOrderStatus
+    );
+
+    // Redeemed or partly used services were delivered, even if not marked fulfilled
+    let asset_info = ctx.accounts.asset.to_account_info();
+    require!(
+        get_attribute(&asset_info, ATTRIBUTE_REDEEMED)?.is_none(),
+        ServiceOfferingError::AlreadyRedeemed
+    );
+    require!(
+        get_remaining_uses(&asset_info)? == ctx.accounts.service_offering.uses_per_asset,
+        ServiceOfferingError::UsesAlreadyConsumed
+    );
+
+    // Read before the burn, to free up the tier's supply as well
+    let tier = get_tier(&asset_info)?;
+
+    // The buyer gives up the service NFT, so it can't be used or resold
+    burn_asset(
+        &asset_info,
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.offering_group_asset.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+    )?;
+
+    ctx.accounts.service_offering.decrement_sold(tier);
+
+    // The refunded purchase no longer counts towards the buyer's limits
+    if ctx.accounts.order_escrow.purchase_counted {
+        ctx.accounts
+            .purchase_counter
+            .as_mut()
+            .ok_or(ServiceOfferingError::MissingPurchaseCounter)?
+            .cancel_purchase();
+    }
+    if let Some(coupon) = ctx.accounts.coupon.as_mut() {
+        coupon.cancel_redemption();
+    }
+
+    // The escrowed payment goes back to the buyer when the escrow is closed
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    transfer_lamports, MarketplaceConfig, MarketplaceError, OrderEscrow, OrderStatus,
+    ServiceOfferingError, SEED_MARKETPLACE_CONFIG, SEED_ORDER_ESCROW,
+};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    // The arbiter assigned to the order
+    pub arbiter: Signer<'info>,
+
+    // The buyer (receives the buyer share and the escrow rent back)
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    // The vendor (receives the vendor share)
+    #[account(mut)]
+    pub vendor: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        has_one = arbiter @ ServiceOfferingError::InvalidArbiter,
+        has_one = buyer,
+        has_one = vendor
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+
+    // Global marketplace config (treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee on the vendor share
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ResolveDispute>, buyer_bps: u16) -> Result<()> {
+    let order_escrow = &ctx.accounts.order_escrow;
+
+    require!(
+        order_escrow.status == OrderStatus::Disputed,
+        ServiceOfferingError::OrderNotDisputed
+    );
+    // Also covers a vendor that is the global arbiter
+    require_keys_neq!(
+        ctx.accounts.arbiter.key(),
+        order_escrow.vendor,
+        ServiceOfferingError::VendorCannotArbitrate
+    );
+
+    let (buyer_amount, vendor_amount, fee) = order_escrow.dispute_split(buyer_bps)?;
+    let escrow_info = order_escrow.to_account_info();
+
+    transfer_lamports(&escrow_info, &ctx.accounts.buyer.to_account_info(), buyer_amount)?;
+    transfer_lamports(&escrow_info, &ctx.accounts.vendor.to_account_info(), vendor_amount)?;
+    transfer_lamports(&escrow_info, &ctx.accounts.treasury.to_account_info(), fee)?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{RegisteredReferrer, ServiceOffering, SEED_REGISTERED_REFERRER, SEED_SERVICE_OFFERING};
+
+#[derive(Accounts)]
+#[instruction(offering_name: String)]
+pub struct RevokeReferrer<'info> {
+    // The vendor that owns the service offering (receives the registration rent back)
+    #[account(mut)]
+    pub vendor: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SERVICE_OFFERING.as_bytes(),
+            vendor.key().as_ref(),
+            offering_name.as_bytes()
+        ],
+        bump = service_offering.bump,
+        has_one = vendor
+    )]
+    pub service_offering: Account<'info, ServiceOffering>,
+
+    // The registration being revoked
+    #[account(
+        mut,
+        close = vendor,
+        has_one = service_offering,
+        seeds = [
+            SEED_REGISTERED_REFERRER.as_bytes(),
+            service_offering.key().as_ref(),
+            registered_referrer.referrer.as_ref(),
+        ],
+        bump = registered_referrer.bump
+    )]
+    pub registered_referrer: Account<'info, RegisteredReferrer>,
+}
+
+pub(crate) fn handler(_ctx: Context<RevokeReferrer>, _offering_name: String) -> Result<()> {
+    // Closing the registration stops further payouts to the referrer
+    Ok(())
+}
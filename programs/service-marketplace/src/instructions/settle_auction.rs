@@ -0,0 +1,163 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use nifty_asset::{accounts::Asset, instructions::UnlockBuilder, ID as NIFTY_ASSET_PROGRAM_ID};
+
+use crate::{
+    calculate_royalties, pay_escrowed_royalties, transfer_asset, transfer_lamports,
+    unlock_and_revoke, Auction, AuctionError, ListingError, MarketplaceConfig, MarketplaceError,
+    SEED_AUCTION, SEED_MARKETPLACE_CONFIG,
+};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    // Anyone can settle an auction once it has ended
+    pub payer: Signer<'info>,
+
+    // The seller (receives the proceeds and the auction rent back)
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    // The highest bidder (only when the auction received bids)
+    /// CHECK: must match the highest bidder stored on the auction
+    #[account(mut)]
+    pub winner: Option<UncheckedAccount<'info>>,
+
+    // The auctioned Asset
+    /// CHECK: must match the asset stored on the auction
+    #[account(
+        mut,
+        address = auction.asset_id @ ListingError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: we are doing some checks in the handler
+    #[account(mut)]
+    pub group_asset: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [
+            SEED_AUCTION.as_bytes(),
+            asset.key().as_ref(),
+            seller.key().as_ref(),
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    // Global marketplace config (protocol fee and treasury)
+    #[account(
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    // The protocol treasury receiving the fee
+    /// CHECK: use address constraint
+    #[account(
+        mut,
+        address = marketplace_config.treasury @ MarketplaceError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: use address constraint
+    #[account(
+        address = NIFTY_ASSET_PROGRAM_ID @ ListingError::InvalidOssProgram
+    )]
+    pub oss_program: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let asset_key = ctx.accounts.asset.key();
+    let seller_key = ctx.accounts.seller.key();
+    let asset_info = ctx.accounts.asset.to_account_info();
+    let auction_info = auction.to_account_info();
+
+    require!(auction.has_ended(), AuctionError::AuctionNotEnded);
+
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        SEED_AUCTION.as_bytes(),
+        asset_key.as_ref(),
+        seller_key.as_ref(),
+        &[auction.bump],
+    ]];
+
+    // No bids: hand the asset back to the seller
+    let Some(winner_key) = auction.highest_bidder else {
+        return unlock_and_revoke(
+            &asset_info,
+            &auction_info,
+            &ctx.accounts.oss_program.to_account_info(),
+            signer_seeds,
+        );
+    };
+
+    let winner = ctx
+        .accounts
+        .winner
+        .as_ref()
+        .ok_or(AuctionError::InvalidWinner)?;
+    require_keys_eq!(winner.key(), winner_key, AuctionError::InvalidWinner);
+
+    let asset = Asset::try_from(&asset_info)?;
+    require!(
+        asset.group.to_option() == Some(ctx.accounts.group_asset.key()),
+        ListingError::InvalidGroup
+    );
+
+    // Process payment out of the auction escrow
+    let (royalty_shares, seller_amount) = calculate_royalties(
+        &ctx.accounts.group_asset.to_account_info(),
+        auction.highest_bid,
+    )?;
+
+    let fee = ctx
+        .accounts
+        .marketplace_config
+        .secondary_fee(auction.highest_bid)?;
+    let seller_amount = seller_amount
+        .checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Creator accounts are passed as remaining accounts (unpayable dust shares go to the seller)
+    let withheld = pay_escrowed_royalties(&auction_info, ctx.remaining_accounts, &royalty_shares)?;
+    let seller_amount = seller_amount
+        .checked_add(withheld)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    transfer_lamports(&auction_info, &ctx.accounts.treasury.to_account_info(), fee)?;
+    transfer_lamports(
+        &auction_info,
+        &ctx.accounts.seller.to_account_info(),
+        seller_amount,
+    )?;
+
+    // Unlock the asset and transfer it to the winner
+
+    let unlock_ix = UnlockBuilder::new()
+        .asset(asset_key)
+        .signer(auction_info.key())
+        .instruction();
+
+    invoke_signed(
+        &unlock_ix,
+        &[
+            asset_info.clone(),
+            auction_info.clone(),
+            ctx.accounts.oss_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    transfer_asset(
+        &asset_info,
+        &auction_info,
+        &winner.to_account_info(),
+        &ctx.accounts.group_asset.to_account_info(),
+        &ctx.accounts.oss_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
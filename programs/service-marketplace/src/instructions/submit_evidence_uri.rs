@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{OrderEscrow, ServiceOfferingError, SEED_ORDER_ESCROW};
+
+#[derive(Accounts)]
+pub struct SubmitEvidenceUri<'info> {
+    // The buyer or vendor of the order
+    pub party: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_ORDER_ESCROW.as_bytes(),
+            order_escrow.asset_id.as_ref(),
+        ],
+        bump = order_escrow.bump,
+        constraint = order_escrow.is_party(&party.key()) @ ServiceOfferingError::NotOrderParty
+    )]
+    pub order_escrow: Account<'info, OrderEscrow>,
+}
+
+pub(crate) fn handler(ctx: Context<SubmitEvidenceUri>, uri: String) -> Result<()> {
+    let party = ctx.accounts.party.key();
+
+    // Each party keeps one evidence URI, resubmitting replaces it
+    ctx.accounts.order_escrow.submit_evidence_uri(&party, uri)
+}
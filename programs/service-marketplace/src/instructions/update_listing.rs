@@ -0,0 +1,63 @@
+use crate::{Listing, ListingError, ListingPricing, ListingUpdated, SEED_LISTING};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateListing<'info> {
+    // The seller
+    pub seller: Signer<'info>,
+
+    // The listing PDA being updated
+    #[account(
+        mut,
+        has_one = seller,
+        seeds = [
+            SEED_LISTING.as_bytes(),
+            listing.asset_id.as_ref(),
+            seller.key().as_ref(),
+        ],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateListing>,
+    price: Option<u64>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+
+    require!(listing.is_active(), ListingError::ListingNotActive);
+    // Updating would redraw the curve over the time already elapsed
+    require!(
+        listing.pricing == ListingPricing::Fixed,
+        ListingError::DecliningListingLocked
+    );
+
+    if let Some(price) = price {
+        listing.update_price(price);
+    }
+
+    // The expiry can be set or extended, but never brought forward
+    if let Some(expiry) = expires_at {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            ListingError::InvalidExpiry
+        );
+        require!(
+            listing.expires_at.is_none_or(|current| expiry >= current),
+            ListingError::ExpiryNotExtended
+        );
+        listing.update_expires_at(Some(expiry));
+    }
+
+    emit!(ListingUpdated {
+        listing: listing.key(),
+        asset_id: listing.asset_id,
+        seller: listing.seller,
+        price: listing.price,
+        expires_at: listing.expires_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{MarketplaceConfig, SEED_MARKETPLACE_CONFIG};
+
+#[derive(Accounts)]
+pub struct UpdateMarketplaceConfig<'info> {
+    // The current config admin
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MARKETPLACE_CONFIG.as_bytes()],
+        bump = marketplace_config.bump,
+        has_one = admin
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateMarketplaceConfig>,
+    admin: Pubkey,
+    treasury: Pubkey,
+    arbiter: Pubkey,
+    primary_fee_bps: u16,
+    secondary_fee_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .marketplace_config
+        .update(admin, treasury, arbiter, primary_fee_bps, secondary_fee_bps)
+}
@@ -2,13 +2,17 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
+pub use utils::*;
 
 declare_id!("gJ12Bk8QFGQjdhZqHZ2PZqqYx8yi5ryVnuosP9S9m7Z");
 
@@ -16,6 +20,7 @@ declare_id!("gJ12Bk8QFGQjdhZqHZ2PZqqYx8yi5ryVnuosP9S9m7Z");
 pub mod service_marketplace {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_service_offering(
         ctx: Context<CreateServiceOffering>,
         offering_name: String,
@@ -29,6 +34,19 @@ pub mod service_marketplace {
         royalty_basis_points: u64,
         terms_of_service_uri: String,
         is_transferrable: bool,
+        service_type: ServiceType,
+        payment_mint: Option<Pubkey>,
+        token_price: u64,
+        escrow_timeout_secs: Option<i64>,
+        arbiter: Option<Pubkey>,
+        redeem_mode: RedeemMode,
+        uses_per_asset: Option<u64>,
+        allowlist_root: Option<[u8; 32]>,
+        max_per_wallet: u64,
+        starts_at: Option<i64>,
+        tiers: Vec<TierConfig>,
+        referral_bps: u16,
+        creators: Vec<CreatorShare>,
     ) -> Result<()> {
         create_service_offering::handler(
             ctx,
@@ -43,19 +61,304 @@ pub mod service_marketplace {
             royalty_basis_points,
             terms_of_service_uri,
             is_transferrable,
+            service_type,
+            payment_mint,
+            token_price,
+            escrow_timeout_secs,
+            arbiter,
+            redeem_mode,
+            uses_per_asset,
+            allowlist_root,
+            max_per_wallet,
+            starts_at,
+            tiers,
+            referral_bps,
+            creators,
         )
     }
 
-    pub fn buy_service(ctx: Context<BuyService>, offering_name: String) -> Result<()> {
-        buy_service::handler(ctx, offering_name)
+    pub fn buy_service(
+        ctx: Context<BuyService>,
+        offering_name: String,
+        allowlist_proof: Option<AllowlistProof>,
+        tier: Option<u8>,
+        coupon_code: Option<String>,
+    ) -> Result<()> {
+        buy_service::handler(ctx, offering_name, allowlist_proof, tier, coupon_code)
+    }
+
+    pub fn list_asset(
+        ctx: Context<ListAsset>,
+        price: u64,
+        expires_at: Option<i64>,
+        payment_mint: Option<Pubkey>,
+        pricing: ListingPricing,
+    ) -> Result<()> {
+        list_asset::handler(ctx, price, expires_at, payment_mint, pricing)
+    }
+
+    pub fn buy_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyListing<'info>>,
+        max_price: u64,
+    ) -> Result<()> {
+        buy_listing::handler(ctx, max_price)
+    }
+
+    pub fn buy_listings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyListings<'info>>,
+        max_total_price: u64,
+    ) -> Result<()> {
+        buy_listings::handler(ctx, max_total_price)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        cancel_listing::handler(ctx)
+    }
+
+    pub fn close_expired_listing(ctx: Context<CloseExpiredListing>) -> Result<()> {
+        close_expired_listing::handler(ctx)
+    }
+
+    pub fn update_listing(
+        ctx: Context<UpdateListing>,
+        price: Option<u64>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        update_listing::handler(ctx, price, expires_at)
+    }
+
+    pub fn activate_service_offering(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+    ) -> Result<()> {
+        manage_service_offering::activate_handler(ctx, offering_name)
+    }
+
+    pub fn deactivate_service_offering(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+    ) -> Result<()> {
+        manage_service_offering::deactivate_handler(ctx, offering_name)
+    }
+
+    pub fn update_service_offering_price(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        sol_price: u64,
+    ) -> Result<()> {
+        manage_service_offering::update_sol_price_handler(ctx, offering_name, sol_price)
+    }
+
+    pub fn update_service_offering_token_price(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        token_price: u64,
+    ) -> Result<()> {
+        manage_service_offering::update_token_price_handler(ctx, offering_name, token_price)
+    }
+
+    pub fn update_service_offering_tier_price(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        tier: u8,
+        price: u64,
+    ) -> Result<()> {
+        manage_service_offering::update_tier_price_handler(ctx, offering_name, tier, price)
+    }
+
+    pub fn update_service_offering_max_quantity(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        max_quantity: u64,
+    ) -> Result<()> {
+        manage_service_offering::update_max_quantity_handler(ctx, offering_name, max_quantity)
+    }
+
+    pub fn update_service_offering_allowlist_root(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        manage_service_offering::update_allowlist_root_handler(ctx, offering_name, allowlist_root)
+    }
+
+    pub fn reschedule_service_offering(
+        ctx: Context<ManageServiceOffering>,
+        offering_name: String,
+        starts_at: Option<i64>,
+    ) -> Result<()> {
+        manage_service_offering::reschedule_handler(ctx, offering_name, starts_at)
+    }
+
+    pub fn renew_subscription(ctx: Context<RenewSubscription>, offering_name: String) -> Result<()> {
+        renew_subscription::handler(ctx, offering_name)
+    }
+
+    pub fn check_subscription(ctx: Context<CheckSubscription>) -> Result<bool> {
+        check_subscription::handler(ctx)
+    }
+
+    pub fn initialize_marketplace_config(
+        ctx: Context<InitializeMarketplaceConfig>,
+        admin: Pubkey,
+        treasury: Pubkey,
+        arbiter: Pubkey,
+        primary_fee_bps: u16,
+        secondary_fee_bps: u16,
+    ) -> Result<()> {
+        initialize_marketplace_config::handler(
+            ctx,
+            admin,
+            treasury,
+            arbiter,
+            primary_fee_bps,
+            secondary_fee_bps,
+        )
+    }
+
+    pub fn update_marketplace_config(
+        ctx: Context<UpdateMarketplaceConfig>,
+        admin: Pubkey,
+        treasury: Pubkey,
+        arbiter: Pubkey,
+        primary_fee_bps: u16,
+        secondary_fee_bps: u16,
+    ) -> Result<()> {
+        update_marketplace_config::handler(
+            ctx,
+            admin,
+            treasury,
+            arbiter,
+            primary_fee_bps,
+            secondary_fee_bps,
+        )
+    }
+
+    pub fn mark_fulfilled(ctx: Context<MarkFulfilled>) -> Result<()> {
+        mark_fulfilled::handler(ctx)
+    }
+
+    pub fn confirm_fulfillment(ctx: Context<ConfirmFulfillment>) -> Result<()> {
+        confirm_fulfillment::handler(ctx)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        release_escrow::handler(ctx)
+    }
+
+    pub fn request_refund(ctx: Context<RequestRefund>) -> Result<()> {
+        request_refund::handler(ctx)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        open_dispute::handler(ctx)
+    }
+
+    pub fn submit_evidence_uri(ctx: Context<SubmitEvidenceUri>, uri: String) -> Result<()> {
+        submit_evidence_uri::handler(ctx, uri)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, buyer_bps: u16) -> Result<()> {
+        resolve_dispute::handler(ctx, buyer_bps)
+    }
+
+    pub fn resolve_expired_dispute(ctx: Context<ResolveExpiredDispute>) -> Result<()> {
+        resolve_expired_dispute::handler(ctx)
+    }
+
+    pub fn redeem_service(ctx: Context<RedeemService>, offering_name: String) -> Result<()> {
+        redeem_service::handler(ctx, offering_name)
+    }
+
+    pub fn consume_use(ctx: Context<ConsumeUse>, offering_name: String) -> Result<()> {
+        consume_use::handler(ctx, offering_name)
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, price: u64, expires_at: Option<i64>) -> Result<()> {
+        make_offer::handler(ctx, price, expires_at)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        cancel_offer::handler(ctx)
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>) -> Result<()> {
+        accept_offer::handler(ctx)
+    }
+
+    pub fn place_collection_bid(
+        ctx: Context<PlaceCollectionBid>,
+        price: u64,
+        quantity: u64,
+        expires_at: Option<i64>,
+        min_remaining_uses: u64,
+        tier: Option<u8>,
+    ) -> Result<()> {
+        place_collection_bid::handler(ctx, price, quantity, expires_at, min_remaining_uses, tier)
+    }
+
+    pub fn cancel_collection_bid(ctx: Context<CancelCollectionBid>) -> Result<()> {
+        cancel_collection_bid::handler(ctx)
+    }
+
+    pub fn fill_collection_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillCollectionBid<'info>>,
+    ) -> Result<()> {
+        fill_collection_bid::handler(ctx)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        reserve_price: u64,
+        min_bid_increment: u64,
+        ends_at: i64,
+        extension_secs: i64,
+    ) -> Result<()> {
+        create_auction::handler(ctx, reserve_price, min_bid_increment, ends_at, extension_secs)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        place_bid::handler(ctx, amount)
     }
 
-    pub fn list_asset(ctx: Context<ListAsset>, price: u64, expires_at: Option<i64>) -> Result<()> {
-        list_asset::handler(ctx, price, expires_at)
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        settle_auction::handler(ctx)
+    }
+
+    pub fn create_coupon(
+        ctx: Context<CreateCoupon>,
+        offering_name: String,
+        code_hash: [u8; 32],
+        discount: Discount,
+        max_redemptions: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        create_coupon::handler(
+            ctx,
+            offering_name,
+            code_hash,
+            discount,
+            max_redemptions,
+            expires_at,
+        )
+    }
+
+    pub fn close_coupon(ctx: Context<CloseCoupon>, offering_name: String) -> Result<()> {
+        close_coupon::handler(ctx, offering_name)
+    }
+
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+        offering_name: String,
+        referrer: Pubkey,
+    ) -> Result<()> {
+        register_referrer::handler(ctx, offering_name, referrer)
     }
 
-    pub fn buy_listing(ctx: Context<BuyListing>) -> Result<()> {
-        buy_listing::handler(ctx)
+    pub fn revoke_referrer(ctx: Context<RevokeReferrer>, offering_name: String) -> Result<()> {
+        revoke_referrer::handler(ctx, offering_name)
     }
 }
 
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::AuctionError;
+
+#[account]
+pub struct Auction {
+    // The public key of the seller (owner of the NFT)
+    pub seller: Pubkey,
+
+    // The public key of the asset being auctioned
+    pub asset_id: Pubkey,
+
+    // The minimum first bid in lamports
+    pub reserve_price: u64,
+
+    // The minimum amount (in lamports) each bid must add to the highest bid
+    pub min_bid_increment: u64,
+
+    // The highest bid in lamports (escrowed in this account)
+    pub highest_bid: u64,
+
+    // The public key of the highest bidder, if any
+    pub highest_bidder: Option<Pubkey>,
+
+    // Timestamp when the auction was created
+    pub created_at: i64,
+
+    // Timestamp when the auction ends (pushed back by late bids)
+    pub ends_at: i64,
+
+    // Bids placed within this many seconds of the end extend it to now + extension_secs
+    pub extension_secs: i64,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl Auction {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // seller
+        32 +    // asset_id
+        8 +     // reserve_price
+        8 +     // min_bid_increment
+        8 +     // highest_bid
+        33 +    // highest_bidder (1 byte for Option enum + 32 bytes for Pubkey)
+        8 +     // created_at
+        8 +     // ends_at
+        8 +     // extension_secs
+        1       // bump
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        seller: Pubkey,
+        asset_id: Pubkey,
+        reserve_price: u64,
+        min_bid_increment: u64,
+        ends_at: i64,
+        extension_secs: i64,
+        bump: u8,
+    ) {
+        self.seller = seller;
+        self.asset_id = asset_id;
+        self.reserve_price = reserve_price;
+        self.min_bid_increment = min_bid_increment;
+        self.highest_bid = 0;
+        self.highest_bidder = None;
+        self.created_at = Clock::get().unwrap().unix_timestamp;
+        self.ends_at = ends_at;
+        self.extension_secs = extension_secs;
+        self.bump = bump;
+    }
+
+    // The lowest amount the next bid can be
+    pub fn min_next_bid(&self) -> Result<u64> {
+        match self.highest_bidder {
+            Some(_) => self
+                .highest_bid
+                .checked_add(self.min_bid_increment)
+                .ok_or(ProgramError::ArithmeticOverflow.into()),
+            None => Ok(self.reserve_price),
+        }
+    }
+
+    pub fn place_bid(&mut self, bidder: Pubkey, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now < self.ends_at, AuctionError::AuctionEnded);
+        require!(amount >= self.min_next_bid()?, AuctionError::BidTooLow);
+
+        self.highest_bid = amount;
+        self.highest_bidder = Some(bidder);
+
+        // Anti-sniping: late bids give everyone else time to respond
+        let extended_end = now.saturating_add(self.extension_secs);
+        if extended_end > self.ends_at {
+            self.ends_at = extended_end;
+        }
+
+        Ok(())
+    }
+
+    pub fn has_ended(&self) -> bool {
+        Clock::get().unwrap().unix_timestamp >= self.ends_at
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct CollectionBid {
+    // The public key of the bidder (receives the assets)
+    pub bidder: Pubkey,
+
+    // The public key of the offering group asset the bid is made on
+    pub group_asset: Pubkey,
+
+    // The price per asset in lamports
+    pub price: u64,
+
+    // The number of assets still wanted (price * quantity is escrowed in this account)
+    pub quantity: u64,
+
+    // Timestamp when the bid was placed
+    pub created_at: i64,
+
+    // Optional expiration timestamp after which the bid can't be filled
+    pub expires_at: Option<i64>,
+
+    // The minimum remaining uses of the assets filling the bid (single-use assets have 1)
+    pub min_remaining_uses: u64,
+
+    // Optional tier the assets filling the bid must be of
+    pub tier: Option<u8>,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl CollectionBid {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // bidder
+        32 +    // group_asset
+        8 +     // price
+        8 +     // quantity
+        8 +     // created_at
+        9 +     // expires_at (1 byte for Option enum + 8 bytes for i64)
+        8 +     // min_remaining_uses
+        2 +     // tier (1 byte for Option enum + 1 byte for u8)
+        1       // bump
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        bidder: Pubkey,
+        group_asset: Pubkey,
+        price: u64,
+        quantity: u64,
+        expires_at: Option<i64>,
+        min_remaining_uses: u64,
+        tier: Option<u8>,
+        bump: u8,
+    ) {
+        self.bidder = bidder;
+        self.group_asset = group_asset;
+        self.price = price;
+        self.quantity = quantity;
+        self.created_at = Clock::get().unwrap().unix_timestamp;
+        self.expires_at = expires_at;
+        self.min_remaining_uses = min_remaining_uses;
+        self.tier = tier;
+        self.bump = bump;
+    }
+
+    // The total amount escrowed for the remaining quantity
+    pub fn escrow_amount(&self) -> Result<u64> {
+        self.price
+            .checked_mul(self.quantity)
+            .ok_or(ProgramError::ArithmeticOverflow.into())
+    }
+
+    pub fn fill(&mut self) {
+        self.quantity = self.quantity.saturating_sub(1);
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.quantity == 0
+    }
+
+    pub fn is_expired(&self) -> bool {
+        if let Some(expiry) = self.expires_at {
+            Clock::get().unwrap().unix_timestamp >= expiry
+        } else {
+            false
+        }
+    }
+}
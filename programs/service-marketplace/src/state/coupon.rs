@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::CouponError;
+
+#[account]
+pub struct Coupon {
+    // The public key of the service offering the coupon applies to
+    pub service_offering: Pubkey,
+
+    // sha256 of the secret code buyers present to redeem the coupon
+    pub code_hash: [u8; 32],
+
+    // The discount applied to the purchase price
+    pub discount: Discount,
+
+    // The maximum number of redemptions (0 for unlimited)
+    pub max_redemptions: u64,
+
+    // The number of times this coupon has been redeemed
+    pub redemptions: u64,
+
+    // Optional expiration timestamp after which the coupon can't be redeemed
+    pub expires_at: Option<i64>,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+pub enum Discount {
+    // Basis points off the price (10_000 makes the purchase free)
+    Percentage { bps: u16 },
+    // Amount off the price, in lamports or in `payment_mint` units
+    Fixed { amount: u64 },
+}
+
+impl Coupon {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // service_offering
+        32 +    // code_hash
+        9 +     // discount (1 byte for enum + 8 bytes for the largest variant)
+        8 +     // max_redemptions
+        8 +     // redemptions
+        9 +     // expires_at (1 byte for Option enum + 8 bytes for i64)
+        1       // bump
+    }
+
+    pub fn create(
+        &mut self,
+        service_offering: Pubkey,
+        code_hash: [u8; 32],
+        discount: Discount,
+        max_redemptions: u64,
+        expires_at: Option<i64>,
+        bump: u8,
+    ) {
+        self.service_offering = service_offering;
+        self.code_hash = code_hash;
+        self.discount = discount;
+        self.max_redemptions = max_redemptions;
+        self.redemptions = 0;
+        self.expires_at = expires_at;
+        self.bump = bump;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        if let Some(expiry) = self.expires_at {
+            Clock::get().unwrap().unix_timestamp >= expiry
+        } else {
+            false
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.max_redemptions > 0 && self.redemptions >= self.max_redemptions
+    }
+
+    // The price after the discount (never below zero)
+    pub fn discounted_price(&self, price: u64) -> Result<u64> {
+        let discount = match self.discount {
+            Discount::Percentage { bps } => (price as u128)
+                .checked_mul(bps as u128)
+                .and_then(|product| product.checked_div(10_000))
+                .and_then(|amount| u64::try_from(amount).ok())
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            Discount::Fixed { amount } => amount,
+        };
+
+        Ok(price.saturating_sub(discount))
+    }
+
+    // Redeems the coupon once, checking the code against the stored hash
+    pub fn redeem(&mut self, code_hash: [u8; 32]) -> Result<()> {
+        require!(code_hash == self.code_hash, CouponError::InvalidCode);
+        require!(!self.is_expired(), CouponError::CouponExpired);
+        require!(!self.is_exhausted(), CouponError::CouponExhausted);
+
+        self.redemptions = self
+            .redemptions
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Gives back a redemption of a refunded order
+    pub fn cancel_redemption(&mut self) {
+        self.redemptions = self.redemptions.saturating_sub(1);
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::ListingError;
 
 #[account]
 pub struct Listing {
@@ -8,43 +9,72 @@ pub struct Listing {
     // The public key of the asset being sold
     pub asset_id: Pubkey,
 
-    // The price of the NFT in lamports
+    // The price of the NFT in lamports, or in base units of `payment_mint`
     pub price: u64,
 
+    // How the price evolves over the listing (fixed, or declining from `price`)
+    pub pricing: ListingPricing,
+
+    // Optional SPL token (Token or Token-2022) mint the listing is paid in instead of SOL
+    pub payment_mint: Option<Pubkey>,
+
     // Timestamp when the listing was created
     pub created_at: i64,
 
     // Optional expiration timestamp for time-limited listings
     pub expires_at: Option<i64>,
 
+    // Remaining uses of a multi-use service asset at listing time (locked while listed)
+    pub remaining_uses: Option<u64>,
+
     // The bump used in PDA derivation
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ListingPricing {
+    #[default]
+    Fixed,
+    // Dutch auction: the price declines linearly from `price` at `created_at` to
+    // `end_price` at the last second before `expires_at` (the last second it can
+    // be bought), in steps of `step_secs` (0 for continuous)
+    Declining { end_price: u64, step_secs: i64 },
+}
+
 impl Listing {
     pub fn get_size() -> usize {
         8 +     // discriminator
         32 +    // seller
         32 +    // asset_id
         8 +     // price
+        17 +    // pricing (1 byte for enum + 8 bytes end_price + 8 bytes step_secs)
+        33 +    // payment_mint (1 byte for Option enum + 32 bytes for Pubkey)
         8 +     // created_at
         9 +     // expires_at (1 byte for Option enum + 8 bytes for i64)
+        9 +     // remaining_uses (1 byte for Option enum + 8 bytes for u64)
         1       // bump
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &mut self,
         seller: Pubkey,
         asset_id: Pubkey,
         price: u64,
+        pricing: ListingPricing,
+        payment_mint: Option<Pubkey>,
         expires_at: Option<i64>,
+        remaining_uses: Option<u64>,
         bump: u8,
     ) {
         self.seller = seller;
         self.asset_id = asset_id;
         self.price = price;
+        self.pricing = pricing;
+        self.payment_mint = payment_mint;
         self.created_at = Clock::get().unwrap().unix_timestamp;
         self.expires_at = expires_at;
+        self.remaining_uses = remaining_uses;
         self.bump = bump;
     }
 
@@ -52,7 +82,57 @@ impl Listing {
         self.price = new_price;
     }
 
-    fn is_expired(&self) -> bool {
+    pub fn update_expires_at(&mut self, new_expires_at: Option<i64>) {
+        self.expires_at = new_expires_at;
+    }
+
+    // Declining listings need an end to decline towards, and can't go up
+    pub fn validate_pricing(&self) -> Result<()> {
+        if let ListingPricing::Declining {
+            end_price,
+            step_secs,
+        } = self.pricing
+        {
+            require!(
+                self.expires_at.is_some() && end_price <= self.price && step_secs >= 0,
+                ListingError::InvalidPricing
+            );
+        }
+
+        Ok(())
+    }
+
+    // The price of the listing at `now`. Deterministic so it can be checked
+    // against any clock value.
+    pub fn current_price(&self, now: i64) -> u64 {
+        let (end_price, step_secs, expires_at) = match (self.pricing, self.expires_at) {
+            (ListingPricing::Declining { end_price, step_secs }, Some(expires_at)) => {
+                (end_price, step_secs, expires_at)
+            }
+            _ => return self.price,
+        };
+
+        // The listing is inactive from `expires_at`, so the decline ends a second earlier
+        let ends_at = expires_at - 1;
+
+        if now <= self.created_at {
+            return self.price;
+        }
+        if now >= ends_at {
+            return end_price;
+        }
+
+        let duration = (ends_at - self.created_at) as u128;
+        let mut elapsed = (now - self.created_at) as u128;
+        if step_secs > 0 {
+            elapsed -= elapsed % step_secs as u128;
+        }
+
+        let decline = (self.price.saturating_sub(end_price) as u128) * elapsed / duration;
+        self.price - decline as u64
+    }
+
+    pub fn is_expired(&self) -> bool {
         if let Some(expiry) = self.expires_at {
             Clock::get().unwrap().unix_timestamp >= expiry
         } else {
@@ -64,3 +144,95 @@ impl Listing {
         !self.is_expired()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREATED_AT: i64 = 1_000;
+    const EXPIRES_AT: i64 = 2_001;
+
+    fn declining_listing(step_secs: i64) -> Listing {
+        Listing {
+            seller: Pubkey::default(),
+            asset_id: Pubkey::default(),
+            price: 10_000,
+            pricing: ListingPricing::Declining {
+                end_price: 2_000,
+                step_secs,
+            },
+            payment_mint: None,
+            created_at: CREATED_AT,
+            expires_at: Some(EXPIRES_AT),
+            remaining_uses: None,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn fixed_price_ignores_the_clock() {
+        let listing = Listing {
+            pricing: ListingPricing::Fixed,
+            ..declining_listing(0)
+        };
+
+        assert_eq!(listing.current_price(CREATED_AT), 10_000);
+        assert_eq!(listing.current_price(EXPIRES_AT), 10_000);
+    }
+
+    #[test]
+    fn declining_price_starts_at_price_and_ends_at_end_price() {
+        let listing = declining_listing(0);
+
+        assert_eq!(listing.current_price(CREATED_AT - 1), 10_000);
+        assert_eq!(listing.current_price(CREATED_AT), 10_000);
+        assert_eq!(listing.current_price(EXPIRES_AT - 2), 2_008);
+        assert_eq!(listing.current_price(EXPIRES_AT), 2_000);
+        assert_eq!(listing.current_price(EXPIRES_AT + 1), 2_000);
+    }
+
+    #[test]
+    fn last_payable_price_is_the_end_price() {
+        // The listing is still active at `expires_at - 1`, its last second
+        assert_eq!(declining_listing(0).current_price(EXPIRES_AT - 1), 2_000);
+        assert_eq!(declining_listing(250).current_price(EXPIRES_AT - 1), 2_000);
+    }
+
+    #[test]
+    fn declining_price_is_linear() {
+        let listing = declining_listing(0);
+
+        assert_eq!(listing.current_price(CREATED_AT + 1), 9_992);
+        assert_eq!(listing.current_price(1_500), 6_000);
+        assert_eq!(listing.current_price(1_750), 4_000);
+    }
+
+    #[test]
+    fn stepped_price_only_drops_on_step_boundaries() {
+        let listing = declining_listing(250);
+
+        assert_eq!(listing.current_price(CREATED_AT + 249), 10_000);
+        assert_eq!(listing.current_price(CREATED_AT + 250), 8_000);
+        assert_eq!(listing.current_price(1_749), 6_000);
+        assert_eq!(listing.current_price(1_750), 4_000);
+        assert_eq!(listing.current_price(EXPIRES_AT - 2), 4_000);
+        assert_eq!(listing.current_price(EXPIRES_AT - 1), 2_000);
+    }
+
+    #[test]
+    fn declining_pricing_requires_an_expiry_and_a_lower_end_price() {
+        assert!(declining_listing(0).validate_pricing().is_ok());
+
+        let no_expiry = Listing {
+            expires_at: None,
+            ..declining_listing(0)
+        };
+        assert!(no_expiry.validate_pricing().is_err());
+
+        let increasing = Listing {
+            price: 1_000,
+            ..declining_listing(0)
+        };
+        assert!(increasing.validate_pricing().is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::{MarketplaceError, MAX_PROTOCOL_FEE_BPS};
+
+#[account]
+pub struct MarketplaceConfig {
+    // The public key allowed to update the config
+    pub admin: Pubkey,
+
+    // The public key receiving protocol fees
+    pub treasury: Pubkey,
+
+    // The default arbiter resolving disputes on escrowed orders
+    pub arbiter: Pubkey,
+
+    // Protocol fee on primary sales (buy_service) in basis points
+    pub primary_fee_bps: u16,
+
+    // Protocol fee on secondary sales (buy_listing) in basis points
+    pub secondary_fee_bps: u16,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl MarketplaceConfig {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // admin
+        32 +    // treasury
+        32 +    // arbiter
+        2 +     // primary_fee_bps
+        2 +     // secondary_fee_bps
+        1       // bump
+    }
+
+    pub fn update(
+        &mut self,
+        admin: Pubkey,
+        treasury: Pubkey,
+        arbiter: Pubkey,
+        primary_fee_bps: u16,
+        secondary_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            primary_fee_bps <= MAX_PROTOCOL_FEE_BPS && secondary_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            MarketplaceError::FeeTooHigh
+        );
+
+        self.admin = admin;
+        self.treasury = treasury;
+        self.arbiter = arbiter;
+        self.primary_fee_bps = primary_fee_bps;
+        self.secondary_fee_bps = secondary_fee_bps;
+        Ok(())
+    }
+
+    pub fn primary_fee(&self, price: u64) -> Result<u64> {
+        Self::fee(price, self.primary_fee_bps)
+    }
+
+    pub fn secondary_fee(&self, price: u64) -> Result<u64> {
+        Self::fee(price, self.secondary_fee_bps)
+    }
+
+    fn fee(price: u64, fee_bps: u16) -> Result<u64> {
+        let fee = (price as u128)
+            .checked_mul(fee_bps as u128)
+            .and_then(|product| product.checked_div(10_000))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(fee as u64)
+    }
+}
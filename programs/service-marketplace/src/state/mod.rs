@@ -1,5 +1,25 @@
 pub mod service_offering;
 pub mod listing;
+pub mod subscription;
+pub mod marketplace_config;
+pub mod order_escrow;
+pub mod offer;
+pub mod collection_bid;
+pub mod auction;
+pub mod purchase_counter;
+pub mod coupon;
+pub mod referral_stats;
+pub mod registered_referrer;
 
 pub use service_offering::*;
-pub use listing::*;
\ No newline at end of file
+pub use listing::*;
+pub use subscription::*;
+pub use marketplace_config::*;
+pub use order_escrow::*;
+pub use offer::*;
+pub use collection_bid::*;
+pub use auction::*;
+pub use purchase_counter::*;
+pub use coupon::*;
+pub use referral_stats::*;
+pub use registered_referrer::*;
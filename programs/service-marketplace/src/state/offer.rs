@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Offer {
+    // The public key of the bidder (receives the asset on acceptance)
+    pub bidder: Pubkey,
+
+    // The public key of the asset the offer is made on
+    pub asset_id: Pubkey,
+
+    // The offered price in lamports (escrowed in this account)
+    pub price: u64,
+
+    // Timestamp when the offer was made
+    pub created_at: i64,
+
+    // Optional expiration timestamp after which the offer can't be accepted
+    pub expires_at: Option<i64>,
+
+    // Remaining uses of a multi-use asset when the offer was made
+    pub remaining_uses: Option<u64>,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl Offer {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // bidder
+        32 +    // asset_id
+        8 +     // price
+        8 +     // created_at
+        9 +     // expires_at (1 byte for Option enum + 8 bytes for i64)
+        9 +     // remaining_uses (1 byte for Option enum + 8 bytes for u64)
+        1       // bump
+    }
+
+    pub fn create(
+        &mut self,
+        bidder: Pubkey,
+        asset_id: Pubkey,
+        price: u64,
+        expires_at: Option<i64>,
+        remaining_uses: Option<u64>,
+        bump: u8,
+    ) {
+        self.bidder = bidder;
+        self.asset_id = asset_id;
+        self.price = price;
+        self.created_at = Clock::get().unwrap().unix_timestamp;
+        self.expires_at = expires_at;
+        self.remaining_uses = remaining_uses;
+        self.bump = bump;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        if let Some(expiry) = self.expires_at {
+            Clock::get().unwrap().unix_timestamp >= expiry
+        } else {
+            false
+        }
+    }
+}
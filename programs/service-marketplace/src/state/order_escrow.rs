@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use crate::{ServiceOfferingError, DISPUTE_TIMEOUT_SECS, MAX_EVIDENCE_URI_LENGTH};
+
+#[account]
+pub struct OrderEscrow {
+    // The public key of the buyer (refunded on request_refund)
+    pub buyer: Pubkey,
+
+    // The public key of the vendor (paid on release)
+    pub vendor: Pubkey,
+
+    // The public key of the service offering the order was placed on
+    pub service_offering: Pubkey,
+
+    // The public key of the asset (NFT) minted for the order
+    pub asset_id: Pubkey,
+
+    // The escrowed purchase price in lamports
+    pub amount: u64,
+
+    // The protocol fee (in lamports) taken out of `amount` on release
+    pub fee: u64,
+
+    // The current status of the order
+    pub status: OrderStatus,
+
+    // Seconds after fulfillment before anyone can release the escrow to the vendor
+    pub release_timeout_secs: i64,
+
+    // Timestamp when the order was placed
+    pub created_at: i64,
+
+    // Timestamp when the vendor marked the order as fulfilled
+    pub fulfilled_at: Option<i64>,
+
+    // The public key allowed to resolve a dispute on this order
+    pub arbiter: Pubkey,
+
+    // Timestamp when the order was disputed
+    pub disputed_at: Option<i64>,
+
+    // Evidence submitted by the buyer in a dispute
+    pub buyer_evidence_uri: String,
+
+    // Evidence submitted by the vendor in a dispute
+    pub vendor_evidence_uri: String,
+
+    // The coupon redeemed for the order (its redemption is given back on refund)
+    pub coupon: Option<Pubkey>,
+
+    // Whether the order was counted on the buyer's purchase counter (undone on refund)
+    pub purchase_counted: bool,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+pub enum OrderStatus {
+    // Paid into escrow, waiting for the vendor to deliver
+    Paid,
+    // The vendor marked the service as delivered
+    Fulfilled,
+    // The buyer or vendor disputed the order, waiting for the arbiter
+    Disputed,
+}
+
+impl OrderEscrow {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // buyer
+        32 +    // vendor
+        32 +    // service_offering
+        32 +    // asset_id
+        8 +     // amount
+        8 +     // fee
+        1 +     // status
+        8 +     // release_timeout_secs
+        8 +     // created_at
+        9 +     // fulfilled_at (1 byte for Option enum + 8 bytes for i64)
+        32 +    // arbiter
+        9 +     // disputed_at (1 byte for Option enum + 8 bytes for i64)
+        4 + MAX_EVIDENCE_URI_LENGTH + // buyer_evidence_uri (4 bytes for length + max length)
+        4 + MAX_EVIDENCE_URI_LENGTH + // vendor_evidence_uri (4 bytes for length + max length)
+        33 +    // coupon (1 byte for Option enum + 32 bytes for Pubkey)
+        1 +     // purchase_counted
+        1       // bump
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        buyer: Pubkey,
+        vendor: Pubkey,
+        service_offering: Pubkey,
+        asset_id: Pubkey,
+        amount: u64,
+        fee: u64,
+        release_timeout_secs: i64,
+        arbiter: Pubkey,
+        coupon: Option<Pubkey>,
+        purchase_counted: bool,
+        bump: u8,
+    ) {
+        self.buyer = buyer;
+        self.vendor = vendor;
+        self.service_offering = service_offering;
+        self.asset_id = asset_id;
+        self.amount = amount;
+        self.fee = fee;
+        self.status = OrderStatus::Paid;
+        self.release_timeout_secs = release_timeout_secs;
+        self.created_at = Clock::get().unwrap().unix_timestamp;
+        self.fulfilled_at = None;
+        self.arbiter = arbiter;
+        self.disputed_at = None;
+        self.buyer_evidence_uri = String::new();
+        self.vendor_evidence_uri = String::new();
+        self.coupon = coupon;
+        self.purchase_counted = purchase_counted;
+        self.bump = bump;
+    }
+
+    pub fn mark_fulfilled(&mut self) {
+        self.status = OrderStatus::Fulfilled;
+        self.fulfilled_at = Some(Clock::get().unwrap().unix_timestamp);
+    }
+
+    pub fn is_party(&self, key: &Pubkey) -> bool {
+        *key == self.buyer || *key == self.vendor
+    }
+
+    pub fn open_dispute(&mut self) -> Result<()> {
+        require!(
+            matches!(self.status, OrderStatus::Paid | OrderStatus::Fulfilled),
+            ServiceOfferingError::InvalidOrderStatus
+        );
+
+        self.status = OrderStatus::Disputed;
+        self.disputed_at = Some(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn submit_evidence_uri(&mut self, party: &Pubkey, uri: String) -> Result<()> {
+        require!(
+            self.status == OrderStatus::Disputed,
+            ServiceOfferingError::OrderNotDisputed
+        );
+        require!(
+            uri.len() <= MAX_EVIDENCE_URI_LENGTH,
+            ServiceOfferingError::EvidenceUriTooLong
+        );
+
+        if *party == self.buyer {
+            self.buyer_evidence_uri = uri;
+        } else {
+            self.vendor_evidence_uri = uri;
+        }
+        Ok(())
+    }
+
+    // Splits the escrow for a resolved dispute into (buyer, vendor, fee) amounts.
+    // The protocol fee is only charged on the part awarded to the vendor.
+    pub fn dispute_split(&self, buyer_bps: u16) -> Result<(u64, u64, u64)> {
+        require!(buyer_bps <= 10_000, ServiceOfferingError::InvalidDisputeSplit);
+
+        let buyer_amount = (self.amount as u128)
+            .checked_mul(buyer_bps as u128)
+            .and_then(|product| product.checked_div(10_000))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let vendor_gross = self
+            .amount
+            .checked_sub(buyer_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let fee = if self.amount == 0 {
+            0
+        } else {
+            (self.fee as u128)
+                .checked_mul(vendor_gross as u128)
+                .and_then(|product| product.checked_div(self.amount as u128))
+                .and_then(|fee| u64::try_from(fee).ok())
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        };
+        let vendor_amount = vendor_gross
+            .checked_sub(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok((buyer_amount, vendor_amount, fee))
+    }
+
+    pub fn vendor_amount(&self) -> Result<u64> {
+        self.amount
+            .checked_sub(self.fee)
+            .ok_or(ProgramError::ArithmeticOverflow.into())
+    }
+
+    // The buyer had `release_timeout_secs` after fulfillment to confirm or object
+    pub fn is_release_timed_out(&self) -> bool {
+        match (self.status, self.fulfilled_at) {
+            (OrderStatus::Fulfilled, Some(fulfilled_at)) => {
+                Clock::get().unwrap().unix_timestamp
+                    >= fulfilled_at.saturating_add(self.release_timeout_secs)
+            }
+            _ => false,
+        }
+    }
+
+    // The arbiter had `DISPUTE_TIMEOUT_SECS` after the dispute was opened to resolve it
+    pub fn is_dispute_timed_out(&self) -> bool {
+        match (self.status, self.disputed_at) {
+            (OrderStatus::Disputed, Some(disputed_at)) => {
+                Clock::get().unwrap().unix_timestamp
+                    >= disputed_at.saturating_add(DISPUTE_TIMEOUT_SECS)
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PurchaseCounter {
+    // The public key of the service offering the purchases were made on
+    pub service_offering: Pubkey,
+
+    // The public key of the buyer
+    pub buyer: Pubkey,
+
+    // The number of assets this buyer has purchased from the offering
+    pub purchased: u64,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl PurchaseCounter {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // service_offering
+        32 +    // buyer
+        8 +     // purchased
+        1       // bump
+    }
+
+    // Counts one purchase, initializing the counter on the buyer's first purchase
+    pub fn record_purchase(
+        &mut self,
+        service_offering: Pubkey,
+        buyer: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        self.service_offering = service_offering;
+        self.buyer = buyer;
+        self.bump = bump;
+        self.purchased = self
+            .purchased
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Takes back a refunded purchase
+    pub fn cancel_purchase(&mut self) {
+        self.purchased = self.purchased.saturating_sub(1);
+    }
+}
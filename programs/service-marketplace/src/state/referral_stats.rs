@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ReferralStats {
+    // The public key of the referrer
+    pub referrer: Pubkey,
+
+    // The SPL token mint the payouts are made in (None for SOL)
+    pub payment_mint: Option<Pubkey>,
+
+    // The number of purchases referred
+    pub referrals: u64,
+
+    // The total referral payouts received, in lamports or in the payment mint's base units
+    pub amount_earned: u64,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl ReferralStats {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // referrer
+        33 +    // payment_mint (1 byte for Option enum + 32 bytes for Pubkey)
+        8 +     // referrals
+        8 +     // amount_earned
+        1       // bump
+    }
+
+    // Sets up the stats on the referrer's first registration. Later registrations
+    // on other offerings in the same currency share the stats and keep the counters.
+    pub fn init(&mut self, referrer: Pubkey, payment_mint: Option<Pubkey>, bump: u8) {
+        self.referrer = referrer;
+        self.payment_mint = payment_mint;
+        self.bump = bump;
+    }
+
+    // Counts one referred purchase
+    pub fn record_referral(&mut self, amount: u64) -> Result<()> {
+        self.referrals = self
+            .referrals
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.amount_earned = self
+            .amount_earned
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
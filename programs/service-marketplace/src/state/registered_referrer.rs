@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct RegisteredReferrer {
+    // The public key of the service offering the referrer is registered on
+    pub service_offering: Pubkey,
+
+    // The public key of the referrer allowed to earn referral payouts
+    pub referrer: Pubkey,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl RegisteredReferrer {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // service_offering
+        32 +    // referrer
+        1       // bump
+    }
+
+    pub fn create(&mut self, service_offering: Pubkey, referrer: Pubkey, bump: u8) {
+        self.service_offering = service_offering;
+        self.referrer = referrer;
+        self.bump = bump;
+    }
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::ServiceOfferingError;
+use crate::{ServiceOfferingError, MAX_CREATORS, MAX_TIERS};
 
 #[account]
 pub struct ServiceOffering {
@@ -9,7 +9,7 @@ pub struct ServiceOffering {
     // The public key of the associated NFT asset
     pub asset_id: Pubkey,
 
-    // The type of service (OneTime, potentially Subscription in the future)
+    // The type of service (OneTime or Subscription)
     pub service_type: ServiceType,
 
     // The number of times this service has been sold
@@ -18,35 +18,114 @@ pub struct ServiceOffering {
     // The maximum number of times this service can be sold (0 for unlimited)
     pub max_quantity: u64,
 
+    // The maximum number of assets a single wallet can buy (0 for unlimited)
+    pub max_per_wallet: u64,
+
     // Whether the service offering is currently active and available for purchase
     pub active: bool,
 
     // The price of the service in lamports (1 SOL = 1_000_000_000 lamports)
     pub sol_price: u64,
 
+    // Optional SPL token (Token or Token-2022) mint the service is paid in instead of SOL
+    pub payment_mint: Option<Pubkey>,
+
+    // The price of the service in base units of `payment_mint`
+    pub token_price: u64,
+
     // Timestamp when the service was created (useful for sorting and tracking)
     pub created_at: i64,
 
     // Optional expiration timestamp for time-limited offerings
     pub expires_at: Option<i64>,
 
+    // Optional timestamp before which the offering can't be bought (scheduled launches)
+    pub starts_at: Option<i64>,
+
     // Whether services are transferable or not
     pub is_transferrable: bool,
 
+    // When set, purchases are held in an order escrow until the buyer confirms
+    // fulfillment, or this many seconds after the vendor marks it fulfilled
+    pub escrow_timeout_secs: Option<i64>,
+
+    // Optional arbiter for disputes on escrowed orders (defaults to the marketplace arbiter)
+    pub arbiter: Option<Pubkey>,
+
+    // How assets are consumed on redeem_service (marked with an attribute or burned)
+    pub redeem_mode: RedeemMode,
+
+    // Optional number of uses each asset carries (e.g. 10 coaching sessions)
+    pub uses_per_asset: Option<u64>,
+
+    // Optional variants of the service (e.g. Basic / Pro / Enterprise), each with
+    // its own price and supply. Empty for single-tier offerings.
+    pub tiers: Vec<ServiceTier>,
+
+    // Share of the vendor proceeds paid to the referrer of a purchase, in basis points
+    pub referral_bps: u16,
+
+    // Optional Merkle root of the buyers allowed to purchase (private cohorts)
+    pub allowlist_root: Option<[u8; 32]>,
+
     // The bump used in PDA derivation
     pub bump: u8
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Default)]
 pub enum ServiceType {
+    #[default]
     OneTime,
-    // Subscription, // TODO: Add subscription
+    // The first period is paid at the offering price, each renewal with `renewal_price`
+    // (in lamports, or in `payment_mint` units for token-priced offerings)
+    Subscription { period_secs: i64, renewal_price: u64 },
+}
+
+// A creator's share (in percent) of the secondary-sale royalties
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    pub share: u8,
 }
 
-impl Default for ServiceType {
-    fn default() -> Self {
-        Self::OneTime
+// A tier as configured by the vendor in create_service_offering
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+pub struct TierConfig {
+    pub price: u64,
+    pub max_quantity: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ServiceTier {
+    // The price of the tier (in lamports, or in `payment_mint` units for token-priced offerings)
+    pub price: u64,
+
+    // The maximum number of times this tier can be sold (0 for unlimited)
+    pub max_quantity: u64,
+
+    // The number of times this tier has been sold
+    pub num_sold: u64,
+}
+
+impl ServiceTier {
+    pub fn get_size() -> usize {
+        8 +     // price
+        8 +     // max_quantity
+        8       // num_sold
     }
+
+    pub fn is_sold_out(&self) -> bool {
+        self.max_quantity > 0 && self.num_sold >= self.max_quantity
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RedeemMode {
+    // Keep the asset and set the `redeemed` attribute on it
+    #[default]
+    Attribute,
+    // Burn the asset
+    Burn,
 }
 
 impl ServiceOffering {
@@ -54,37 +133,72 @@ impl ServiceOffering {
         8 +     // discriminator
         32 +    // vendor
         32 +    // asset_id
-        2 +     // service_type
+        17 +    // service_type (1 byte for enum + 8 bytes period_secs + 8 bytes renewal_price)
         8 +     // num_sold
         8 +     // max_quantity
+        8 +     // max_per_wallet
         1 +     // active
         8 +     // sol_price
+        33 +    // payment_mint (1 byte for Option enum + 32 bytes for Pubkey)
+        8 +     // token_price
         8 +     // created_at
         9 +     // expires_at (1 byte for Option enum + 8 bytes for i64)
+        9 +     // starts_at (1 byte for Option enum + 8 bytes for i64)
         1 +     // is_transferrable
+        9 +     // escrow_timeout_secs (1 byte for Option enum + 8 bytes for i64)
+        33 +    // arbiter (1 byte for Option enum + 32 bytes for Pubkey)
+        1 +     // redeem_mode
+        9 +     // uses_per_asset (1 byte for Option enum + 8 bytes for u64)
+        4 + MAX_TIERS * ServiceTier::get_size() + // tiers (4 bytes for Vec length)
+        2 +     // referral_bps
+        33 +    // allowlist_root (1 byte for Option enum + 32 bytes for the root)
         1       // bump
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &mut self,
         vendor: Pubkey,
         asset_id: Pubkey,
         max_quantity: u64,
+        max_per_wallet: u64,
         sol_price: u64,
+        payment_mint: Option<Pubkey>,
+        token_price: u64,
         expires_at: Option<i64>,
+        starts_at: Option<i64>,
         is_transferrable: bool,
+        service_type: ServiceType,
+        escrow_timeout_secs: Option<i64>,
+        arbiter: Option<Pubkey>,
+        redeem_mode: RedeemMode,
+        uses_per_asset: Option<u64>,
+        tiers: Vec<ServiceTier>,
+        referral_bps: u16,
+        allowlist_root: Option<[u8; 32]>,
         bump: u8,
     ) {
         self.vendor = vendor;
         self.asset_id = asset_id;
-        self.service_type = ServiceType::default();
+        self.service_type = service_type;
         self.num_sold = 0;
         self.active = true;
         self.sol_price = sol_price;
+        self.payment_mint = payment_mint;
+        self.token_price = token_price;
         self.max_quantity = max_quantity;
+        self.max_per_wallet = max_per_wallet;
         self.created_at = Clock::get().unwrap().unix_timestamp;
         self.expires_at = expires_at;
+        self.starts_at = starts_at;
         self.is_transferrable = is_transferrable;
+        self.escrow_timeout_secs = escrow_timeout_secs;
+        self.arbiter = arbiter;
+        self.redeem_mode = redeem_mode;
+        self.uses_per_asset = uses_per_asset;
+        self.tiers = tiers;
+        self.referral_bps = referral_bps;
+        self.allowlist_root = allowlist_root;
         self.bump = bump;
     }
 
@@ -96,12 +210,106 @@ impl ServiceOffering {
         self.active = true;
     }
 
-    pub fn update_sol_price(&mut self, new_price: u64) {
+    // Tiered offerings are repriced per tier, token-priced ones in their mint
+    pub fn update_sol_price(&mut self, new_price: u64) -> Result<()> {
+        require!(
+            self.payment_mint.is_none() && self.tiers.is_empty(),
+            ServiceOfferingError::PriceNotUpdatable
+        );
         self.sol_price = new_price;
+        Ok(())
+    }
+
+    pub fn update_token_price(&mut self, new_price: u64) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() && self.tiers.is_empty(),
+            ServiceOfferingError::PriceNotUpdatable
+        );
+        self.token_price = new_price;
+        Ok(())
+    }
+
+    // Tier prices are in the offering's currency (lamports or `payment_mint` units)
+    pub fn update_tier_price(&mut self, tier: u8, new_price: u64) -> Result<()> {
+        let tier = self
+            .tiers
+            .get_mut(tier as usize)
+            .ok_or(ServiceOfferingError::InvalidTier)?;
+        tier.price = new_price;
+        Ok(())
     }
 
-    pub fn update_max_quantity(&mut self, new_quantity: u64) {
+    // Rotates the allowlist (None opens the offering to everyone)
+    pub fn update_allowlist_root(&mut self, allowlist_root: Option<[u8; 32]>) {
+        self.allowlist_root = allowlist_root;
+    }
+
+    pub fn update_max_quantity(&mut self, new_quantity: u64) -> Result<()> {
+        // 0 means unlimited, otherwise the cap must cover what's already been sold
+        require!(
+            new_quantity == 0 || new_quantity >= self.num_sold,
+            ServiceOfferingError::MaxQuantityBelowSold
+        );
+
         self.max_quantity = new_quantity;
+        Ok(())
+    }
+
+    // The purchase price in the offering's payment currency (lamports or `payment_mint` units)
+    pub fn price(&self) -> u64 {
+        if self.payment_mint.is_some() {
+            self.token_price
+        } else {
+            self.sol_price
+        }
+    }
+
+    // Moves the start of the sale, only while it hasn't started (None opens it now)
+    pub fn reschedule(&mut self, starts_at: Option<i64>) -> Result<()> {
+        require!(!self.has_started(), ServiceOfferingError::SaleAlreadyStarted);
+        validate_starts_at(starts_at, self.expires_at)?;
+
+        self.starts_at = starts_at;
+        Ok(())
+    }
+
+    pub fn has_started(&self) -> bool {
+        if let Some(start) = self.starts_at {
+            Clock::get().unwrap().unix_timestamp >= start
+        } else {
+            true
+        }
+    }
+
+    // The purchase price of `tier`, which must be set exactly when the offering has tiers
+    pub fn tier_price(&self, tier: Option<u8>) -> Result<u64> {
+        match tier {
+            None if self.tiers.is_empty() => Ok(self.price()),
+            Some(index) => self
+                .tiers
+                .get(index as usize)
+                .map(|tier| tier.price)
+                .ok_or_else(|| error!(ServiceOfferingError::InvalidTier)),
+            None => err!(ServiceOfferingError::InvalidTier),
+        }
+    }
+
+    // Untiered subscriptions renew at `renewal_price`, tiered ones at their tier's price
+    pub fn renewal_price(&self, tier: Option<u8>) -> Result<u64> {
+        match self.service_type {
+            ServiceType::Subscription { renewal_price, .. } if tier.is_none() => Ok(renewal_price),
+            ServiceType::Subscription { .. } => self.tier_price(tier),
+            ServiceType::OneTime => err!(ServiceOfferingError::NotASubscription),
+        }
+    }
+
+    // The referrer's cut of the vendor proceeds
+    pub fn referral_amount(&self, vendor_amount: u64) -> Result<u64> {
+        (vendor_amount as u128)
+            .checked_mul(self.referral_bps as u128)
+            .and_then(|product| product.checked_div(10_000))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or_else(|| ProgramError::ArithmeticOverflow.into())
     }
 
     pub fn is_expired(&self) -> bool {
@@ -112,23 +320,102 @@ impl ServiceOffering {
         }
     }
 
+    // Renewals are allowed while the vendor keeps the offering live, even if it is sold out
+    pub fn accepts_renewals(&self) -> bool {
+        self.active && !self.is_expired()
+    }
+
+    // Whether a wallet that already bought `purchased` assets may buy another
+    pub fn is_within_wallet_limit(&self, purchased: u64) -> bool {
+        self.max_per_wallet == 0 || purchased < self.max_per_wallet
+    }
+
     pub fn is_sold_out(&self) -> bool {
         self.max_quantity > 0 && self.num_sold >= self.max_quantity
     }
 
     pub fn is_active(&self) -> bool {
-        self.active && !self.is_expired() && !self.is_sold_out()
+        self.active && self.has_started() && !self.is_expired() && !self.is_sold_out()
     }
 
-    pub fn increment_sold(&mut self) -> Result<()> {
+    // Frees up supply again when an escrowed order is refunded
+    pub fn decrement_sold(&mut self, tier: Option<u8>) {
+        self.num_sold = self.num_sold.saturating_sub(1);
+
+        if let Some(tier) = tier.and_then(|index| self.tiers.get_mut(index as usize)) {
+            tier.num_sold = tier.num_sold.saturating_sub(1);
+        }
+    }
+
+    pub fn increment_sold(&mut self, tier: Option<u8>) -> Result<()> {
+        require!(self.has_started(), ServiceOfferingError::SaleNotStarted);
         require!(self.is_active(), ServiceOfferingError::ServiceNotActive);
         require!(!self.is_sold_out(), ServiceOfferingError::SoldOut);
 
+        if let Some(index) = tier {
+            let tier = self
+                .tiers
+                .get_mut(index as usize)
+                .ok_or(ServiceOfferingError::InvalidTier)?;
+            require!(!tier.is_sold_out(), ServiceOfferingError::TierSoldOut);
+            tier.num_sold += 1;
+        }
+
         self.num_sold += 1;
         Ok(())
     }
 }
 
+// Tiers are priced and capped by the vendor, and start unsold
+pub fn build_tiers(tiers: Vec<TierConfig>) -> Result<Vec<ServiceTier>> {
+    require!(tiers.len() <= MAX_TIERS, ServiceOfferingError::TooManyTiers);
+
+    Ok(tiers
+        .into_iter()
+        .map(|tier| ServiceTier {
+            price: tier.price,
+            max_quantity: tier.max_quantity,
+            num_sold: 0,
+        })
+        .collect())
+}
+
+// Creators must be unique and their shares must add up to 100
+pub fn validate_creator_shares(creators: &[CreatorShare]) -> Result<()> {
+    require!(
+        creators.len() <= MAX_CREATORS,
+        ServiceOfferingError::TooManyCreators
+    );
+
+    let total: u32 = creators.iter().map(|creator| creator.share as u32).sum();
+    let unique = creators.iter().enumerate().all(|(i, creator)| {
+        creators[..i]
+            .iter()
+            .all(|other| other.address != creator.address)
+    });
+    require!(
+        total == 100 && unique,
+        ServiceOfferingError::InvalidCreatorShares
+    );
+
+    Ok(())
+}
+
+// A scheduled start must be in the future and before the expiry
+pub fn validate_starts_at(starts_at: Option<i64>, expires_at: Option<i64>) -> Result<()> {
+    if let Some(start) = starts_at {
+        require!(
+            start > Clock::get()?.unix_timestamp,
+            ServiceOfferingError::InvalidStartTime
+        );
+        if let Some(expiry) = expires_at {
+            require!(start < expiry, ServiceOfferingError::InvalidStartTime);
+        }
+    }
+
+    Ok(())
+}
+
 /*
 
 Several pieces of data to store in the Asset (NFT) account (not in the ServiceOffering account):
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Subscription {
+    // The public key of the service offering the subscription belongs to
+    pub service_offering: Pubkey,
+
+    // The public key of the subscription asset (NFT)
+    pub asset_id: Pubkey,
+
+    // Timestamp until which the subscription has been paid
+    pub paid_through: i64,
+
+    // The tier the subscription was bought in (renewed at that tier's price)
+    pub tier: Option<u8>,
+
+    // The bump used in PDA derivation
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub fn get_size() -> usize {
+        8 +     // discriminator
+        32 +    // service_offering
+        32 +    // asset_id
+        8 +     // paid_through
+        2 +     // tier (1 byte for Option enum + 1 byte for u8)
+        1       // bump
+    }
+
+    pub fn create(
+        &mut self,
+        service_offering: Pubkey,
+        asset_id: Pubkey,
+        period_secs: i64,
+        tier: Option<u8>,
+        bump: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.service_offering = service_offering;
+        self.asset_id = asset_id;
+        self.paid_through = now
+            .checked_add(period_secs)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.tier = tier;
+        self.bump = bump;
+        Ok(())
+    }
+
+    // A lapsed subscription restarts from now, a current one is extended from `paid_through`
+    pub fn renew(&mut self, period_secs: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.paid_through = self
+            .paid_through
+            .max(now)
+            .checked_add(period_secs)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn is_current(&self) -> bool {
+        Clock::get().unwrap().unix_timestamp < self.paid_through
+    }
+}
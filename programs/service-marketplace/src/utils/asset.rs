@@ -0,0 +1,228 @@
+use crate::{ServiceOfferingError, ATTRIBUTE_REMAINING_USES, ATTRIBUTE_TIER};
+use anchor_lang::{
+    prelude::*,
+    solana_program::program::{invoke, invoke_signed},
+};
+use nifty_asset::{
+    extensions::{Attributes, AttributesBuilder, ExtensionBuilder},
+    instructions::{
+        ApproveBuilder, BurnBuilder, LockBuilder, RevokeBuilder, TransferBuilder, UnlockBuilder,
+        UpdateBuilder,
+    },
+    state::Asset,
+    types::{DelegateInput, DelegateRole, ExtensionInput, ExtensionType},
+};
+
+// Escrows an asset in place for a listing or auction: the owner approves the
+// delegate PDA for Transfer+Lock, and the delegate then locks the asset.
+pub fn approve_and_lock<'info>(
+    asset: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    delegate: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    oss_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let approve_ix = ApproveBuilder::new()
+        .asset(asset.key())
+        .owner(owner.key())
+        .delegate(delegate.key())
+        .delegate_input(DelegateInput::Some {
+            roles: vec![DelegateRole::Transfer, DelegateRole::Lock],
+        })
+        .instruction();
+
+    invoke(
+        &approve_ix,
+        &[
+            asset.clone(),
+            owner.clone(),
+            delegate.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    let lock_ix = LockBuilder::new()
+        .asset(asset.key())
+        .signer(delegate.key())
+        .instruction();
+
+    invoke_signed(
+        &lock_ix,
+        &[asset.clone(), delegate.clone(), oss_program.clone()],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+// Hands a listed asset back to its owner: the delegate PDA unlocks the asset
+// and then revokes its own Transfer+Lock delegation.
+pub fn unlock_and_revoke<'info>(
+    asset: &AccountInfo<'info>,
+    delegate: &AccountInfo<'info>,
+    oss_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let account_infos = vec![asset.clone(), delegate.clone(), oss_program.clone()];
+
+    let unlock_ix = UnlockBuilder::new()
+        .asset(asset.key())
+        .signer(delegate.key())
+        .instruction();
+
+    invoke_signed(&unlock_ix, &account_infos, signer_seeds)?;
+
+    let revoke_ix = RevokeBuilder::new()
+        .asset(asset.key())
+        .signer(delegate.key())
+        .delegate_input(DelegateInput::All)
+        .instruction();
+
+    invoke_signed(&revoke_ix, &account_infos, signer_seeds)?;
+
+    Ok(())
+}
+
+// Burns an asset signed by its owner, refunding the asset rent to the owner
+pub fn burn_asset<'info>(
+    asset: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    group_asset: &AccountInfo<'info>,
+    oss_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let burn_ix = BurnBuilder::new()
+        .asset(asset.key())
+        .signer(owner.key())
+        .recipient(Some(owner.key()))
+        .group(Some(group_asset.key()))
+        .instruction();
+
+    invoke(
+        &burn_ix,
+        &[
+            asset.clone(),
+            owner.clone(),
+            group_asset.clone(),
+            oss_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Reads a trait from the Attributes extension of an asset
+pub fn get_attribute(asset: &AccountInfo, name: &str) -> Result<Option<String>> {
+    let data = asset.try_borrow_data()?;
+
+    Ok(Asset::get::<Attributes>(&data)
+        .and_then(|attributes| attributes.get(name).map(String::from)))
+}
+
+// Sets a trait on the Attributes extension of an asset, keeping the other traits.
+// The asset authority (the service offering PDA) signs the update.
+#[allow(clippy::too_many_arguments)]
+pub fn set_attribute<'info>(
+    asset: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    oss_program: &AccountInfo<'info>,
+    name: &str,
+    value: &str,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut attributes_builder = AttributesBuilder::default();
+    {
+        let data = asset.try_borrow_data()?;
+        if let Some(attributes) = Asset::get::<Attributes>(&data) {
+            for attribute in attributes.iter() {
+                if attribute.name.as_str() != name {
+                    attributes_builder.add(attribute.name.as_str(), attribute.value.as_str());
+                }
+            }
+        }
+    }
+    attributes_builder.add(name, value);
+    let attributes_data = attributes_builder.data();
+
+    let update_ix = UpdateBuilder::new()
+        .asset(asset.key())
+        .authority(authority.key())
+        .payer(Some(payer.key()))
+        .system_program(Some(system_program.key()))
+        .extension(ExtensionInput {
+            extension_type: ExtensionType::Attributes,
+            length: attributes_data.len() as u32,
+            data: Some(attributes_data),
+        })
+        .instruction();
+
+    invoke_signed(
+        &update_ix,
+        &[
+            asset.clone(),
+            authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            oss_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+// Reads the remaining uses counter of a multi-use service asset
+pub fn get_remaining_uses(asset: &AccountInfo) -> Result<Option<u64>> {
+    get_attribute(asset, ATTRIBUTE_REMAINING_USES)?
+        .map(|value| {
+            value
+                .parse::<u64>()
+                .map_err(|_| error!(ServiceOfferingError::NotMultiUse))
+        })
+        .transpose()
+}
+
+// Reads the tier index of a tiered service asset
+pub fn get_tier(asset: &AccountInfo) -> Result<Option<u8>> {
+    get_attribute(asset, ATTRIBUTE_TIER)?
+        .map(|value| {
+            value
+                .parse::<u8>()
+                .map_err(|_| error!(ServiceOfferingError::InvalidTier))
+        })
+        .transpose()
+}
+
+// Transfers an asset in its group to `recipient`, signed by the owner or a
+// delegate PDA (pass its seeds)
+pub fn transfer_asset<'info>(
+    asset: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    group_asset: &AccountInfo<'info>,
+    oss_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let transfer_ix = TransferBuilder::new()
+        .asset(asset.key())
+        .signer(signer.key())
+        .recipient(recipient.key())
+        .group(Some(group_asset.key()))
+        .instruction();
+
+    invoke_signed(
+        &transfer_ix,
+        &[
+            asset.clone(),
+            signer.clone(),
+            recipient.clone(),
+            oss_program.clone(),
+            group_asset.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
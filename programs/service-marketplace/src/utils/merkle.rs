@@ -0,0 +1,83 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+// Leaf of an offering allowlist: sha256(buyer || allocation as u64 LE).
+// An allocation of 0 means the buyer has no per-address limit.
+pub fn allowlist_leaf(buyer: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[buyer.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+// Verifies a Merkle proof where each pair of nodes is hashed in sorted order,
+// so the proof doesn't need to encode left/right positions
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn verifies_every_leaf_of_a_tree() {
+        let buyers: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = buyers
+            .iter()
+            .enumerate()
+            .map(|(i, buyer)| allowlist_leaf(buyer, i as u64))
+            .collect();
+
+        let left = hash_pair(leaves[0], leaves[1]);
+        let right = hash_pair(leaves[2], leaves[3]);
+        let root = hash_pair(left, right);
+
+        assert!(verify_merkle_proof(&[leaves[1], right], root, leaves[0]));
+        assert!(verify_merkle_proof(&[leaves[0], right], root, leaves[1]));
+        assert!(verify_merkle_proof(&[leaves[3], left], root, leaves[2]));
+        assert!(verify_merkle_proof(&[leaves[2], left], root, leaves[3]));
+    }
+
+    #[test]
+    fn rejects_wrong_allocation_or_buyer() {
+        let buyer = Pubkey::new_unique();
+        let other = allowlist_leaf(&Pubkey::new_unique(), 0);
+        let root = hash_pair(allowlist_leaf(&buyer, 2), other);
+
+        assert!(verify_merkle_proof(
+            &[other],
+            root,
+            allowlist_leaf(&buyer, 2)
+        ));
+        assert!(!verify_merkle_proof(
+            &[other],
+            root,
+            allowlist_leaf(&buyer, 3)
+        ));
+        assert!(!verify_merkle_proof(
+            &[other],
+            root,
+            allowlist_leaf(&Pubkey::new_unique(), 2)
+        ));
+    }
+
+    #[test]
+    fn single_leaf_tree_has_an_empty_proof() {
+        let leaf = allowlist_leaf(&Pubkey::new_unique(), 1);
+
+        assert!(verify_merkle_proof(&[], leaf, leaf));
+    }
+}
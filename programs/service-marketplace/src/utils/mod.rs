@@ -0,0 +1,9 @@
+pub mod asset;
+pub mod merkle;
+pub mod payments;
+pub mod royalties;
+
+pub use asset::*;
+pub use merkle::*;
+pub use payments::*;
+pub use royalties::*;
@@ -0,0 +1,211 @@
+use crate::{transfer_lamports, transfer_tokens, ListingError};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use nifty_asset::{
+    extensions::{Creators, Royalties},
+    state::Asset,
+    ID as NIFTY_ASSET_PROGRAM_ID,
+};
+
+// A single creator's cut of a secondary sale
+pub struct RoyaltyShare {
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+// Splits a secondary sale price into creator royalties and seller proceeds.
+// Royalty basis points and creator shares are read from the Royalties and
+// Creators extensions written on the offering group asset.
+pub fn calculate_royalties(
+    group_asset: &AccountInfo,
+    price: u64,
+) -> Result<(Vec<RoyaltyShare>, u64)> {
+    require_keys_eq!(
+        *group_asset.owner,
+        NIFTY_ASSET_PROGRAM_ID,
+        ListingError::InvalidGroup
+    );
+
+    let data = group_asset.try_borrow_data()?;
+
+    let basis_points = match Asset::get::<Royalties>(&data) {
+        Some(royalties) => *royalties.basis_points,
+        None => return Ok((Vec::new(), price)),
+    };
+
+    let creators = Asset::get::<Creators>(&data).ok_or(ListingError::InvalidCreator)?;
+
+    let royalty_amount = (price as u128)
+        .checked_mul(basis_points as u128)
+        .and_then(|product| product.checked_div(10_000))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let mut shares = Vec::with_capacity(creators.values.len());
+    let mut total_royalties: u64 = 0;
+
+    for creator in creators.values {
+        // Rounding dust stays with the seller
+        let amount = royalty_amount
+            .checked_mul(creator.share as u128)
+            .and_then(|product| product.checked_div(100))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        total_royalties = total_royalties
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        shares.push(RoyaltyShare {
+            creator: creator.address,
+            amount,
+        });
+    }
+
+    let seller_amount = price
+        .checked_sub(total_royalties)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok((shares, seller_amount))
+}
+
+// A lamport payout can't leave the creator below the rent-exempt minimum (a
+// dust share to an empty wallet would fail the whole sale)
+fn is_payable(creator_account: &AccountInfo, amount: u64) -> Result<bool> {
+    let balance = creator_account
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(amount > 0 && balance >= Rent::get()?.minimum_balance(creator_account.data_len()))
+}
+
+// Pays each creator its share in lamports. Creator accounts must be passed in
+// the same order as the group's Creators extension. Returns the total of the
+// shares too small to be paid, which stays with the seller.
+pub fn pay_royalties<'info>(
+    payer: &AccountInfo<'info>,
+    creator_accounts: &[AccountInfo<'info>],
+    shares: &[RoyaltyShare],
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    require!(
+        creator_accounts.len() >= shares.len(),
+        ListingError::InvalidCreator
+    );
+
+    let mut withheld: u64 = 0;
+    for (share, creator_account) in shares.iter().zip(creator_accounts) {
+        require_keys_eq!(
+            creator_account.key(),
+            share.creator,
+            ListingError::InvalidCreator
+        );
+
+        if !is_payable(creator_account, share.amount)? {
+            withheld = withheld
+                .checked_add(share.amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            continue;
+        }
+
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: creator_account.clone(),
+                },
+            ),
+            share.amount,
+        )?;
+    }
+
+    Ok(withheld)
+}
+
+// Pays each creator its share in lamports out of a program-owned escrow account
+// (offers, bids, auctions). Creator accounts must be passed in the same order
+// as the group's Creators extension. Returns the total of the shares too small
+// to be paid, which stays with the seller.
+pub fn pay_escrowed_royalties<'info>(
+    escrow: &AccountInfo<'info>,
+    creator_accounts: &[AccountInfo<'info>],
+    shares: &[RoyaltyShare],
+) -> Result<u64> {
+    require!(
+        creator_accounts.len() >= shares.len(),
+        ListingError::InvalidCreator
+    );
+
+    let mut withheld: u64 = 0;
+    for (share, creator_account) in shares.iter().zip(creator_accounts) {
+        require_keys_eq!(
+            creator_account.key(),
+            share.creator,
+            ListingError::InvalidCreator
+        );
+
+        if !is_payable(creator_account, share.amount)? {
+            withheld = withheld
+                .checked_add(share.amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            continue;
+        }
+
+        transfer_lamports(escrow, creator_account, share.amount)?;
+    }
+
+    Ok(withheld)
+}
+
+// Pays each creator its share in `mint` tokens. Creator token accounts must be
+// passed in the same order as the group's Creators extension.
+pub fn pay_token_royalties<'info>(
+    payer: &AccountInfo<'info>,
+    payer_token_account: &InterfaceAccount<'info, TokenAccount>,
+    creator_token_accounts: &'info [AccountInfo<'info>],
+    shares: &[RoyaltyShare],
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        creator_token_accounts.len() >= shares.len(),
+        ListingError::InvalidCreator
+    );
+
+    for (share, creator_token_account) in shares.iter().zip(creator_token_accounts) {
+        let creator_token_account =
+            InterfaceAccount::<TokenAccount>::try_from(creator_token_account)?;
+
+        require_keys_eq!(
+            creator_token_account.owner,
+            share.creator,
+            ListingError::InvalidCreator
+        );
+        require_keys_eq!(
+            creator_token_account.mint,
+            mint.key(),
+            ListingError::InvalidCreator
+        );
+
+        if share.amount == 0 {
+            continue;
+        }
+
+        transfer_tokens(
+            payer_token_account,
+            &creator_token_account,
+            payer,
+            mint,
+            token_program,
+            share.amount,
+            signer_seeds,
+        )?;
+    }
+
+    Ok(())
+}
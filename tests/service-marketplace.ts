@@ -1,7 +1,20 @@
 import { setupTest } from "./utils/fixtures";
-import { createServiceOffering, fetchServiceOffering, buyService, listAsset, fetchListing, buyListing } from "./utils/transactions";
-import { findListingPDA, findOfferingGroupAssetPDA, findServiceOfferingPDA } from "./utils/pdas";
-import { assert, expect } from "chai";
+import { createServiceOffering, fetchServiceOffering, buyService, listAsset, fetchListing, buyListing, buyListings, simulateBuyListings, MAX_TRANSACTION_COMPUTE_UNITS, cancelListing, closeExpiredListing, updateListing,
+  activateServiceOffering, deactivateServiceOffering, updateServiceOfferingPrice, updateServiceOfferingTokenPrice, updateServiceOfferingTierPrice, updateServiceOfferingMaxQuantity,
+  renewSubscription, checkSubscription, fetchSubscription, updateMarketplaceConfig,
+  markFulfilled, confirmFulfillment, releaseEscrow, requestRefund, fetchOrderEscrow,
+  openDispute, submitEvidenceUri, resolveDispute, resolveExpiredDispute, redeemService, consumeUse,
+  makeOffer, cancelOffer, acceptOffer, fetchOffer,
+  placeCollectionBid, cancelCollectionBid, fillCollectionBid, fetchCollectionBid,
+  createAuction, placeBid, settleAuction, fetchAuction, updateServiceOfferingAllowlistRoot,
+  rescheduleServiceOffering, createCoupon, closeCoupon, fetchCoupon,
+  fetchReferralStats, registerReferrer, revokeReferrer } from "./utils/transactions";
+import { airdropToMultiple, expectAnchorError, fetchAssetHeader, fetchAssetAttribute, hashCouponCode, setupPaymentMint } from "./utils/utils";
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync, getAccount } from "@solana/spl-token";
+import { EXPIRED_LISTING_BOUNTY, MAX_PROTOCOL_FEE_BPS } from "./utils/seeds";
+import { findListingPDA, findOfferingGroupAssetPDA, findServiceOfferingPDA, findSubscriptionPDA, findOrderEscrowPDA, findOfferPDA, findCollectionBidPDA, findAuctionPDA, findPurchaseCounterPDA, findCouponPDA } from "./utils/pdas";
+import { buildAllowlistTree } from "./utils/merkle";
+import { assert } from "chai";
 import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { ServiceMarketplace } from "../target/types/service_marketplace";
 import { Program, BN } from "@coral-xyz/anchor";
@@ -9,20 +22,30 @@ import { Program, BN } from "@coral-xyz/anchor";
 describe("Service Marketplace", () => {
   let program: Program<ServiceMarketplace>;
   let vendor1: Keypair;
+  let vendor2: Keypair;
   let buyer1: Keypair;
   let buyer2: Keypair;
+  let admin: Keypair;
+  let treasury: Keypair;
+
+  // Asserts that a transaction fails with the given Anchor error code
+  const expectError = (transaction: Promise<unknown>, code: string) =>
+    expectAnchorError(program.provider.connection, transaction, code);
 
   before(async () => {
     const setup = await setupTest();
     program = setup.program;
     vendor1 = setup.vendor1;
+    vendor2 = setup.vendor2;
     buyer1 = setup.buyer1;
     buyer2 = setup.buyer2;
+    admin = setup.admin;
+    treasury = setup.treasury;
   });
 
   const offeringDetails = {
     offeringName: "Test Offering",
-    maxQuantity: 10,
+    maxQuantity: 11,
     solPrice: LAMPORTS_PER_SOL,
     expiresAt: null,
     symbol: "TEST",
@@ -95,11 +118,1611 @@ describe("Service Marketplace", () => {
     assert.equal(listingAccount.assetId.toBase58(), newAsset.publicKey.toBase58(), "Asset pubkey doesn't match");
     assert.equal(listingAccount.price.toNumber(), listingDetails.solPrice, "Price doesn't match");
   });
+  it("should fail to buy a listing above the buyer's max price", async () => {
+    await expectError(buyListing(program, listing, buyer2, newAsset, offeringGroupAsset, buyer1, [vendor1.publicKey], null, LAMPORTS_PER_SOL), "MaxPriceExceeded");
+  });
   it("should successfully buy a listing", async () => {
-    const tx = await buyListing(program, listing, buyer2, newAsset, offeringGroupAsset, buyer1, vendor1);
+    const connection = program.provider.connection;
+    const listingAccount = await fetchListing(program, listing);
+    const price = listingAccount.price.toNumber();
+    const vendorBalanceBefore = await connection.getBalance(vendor1.publicKey);
+
+    const tx = await buyListing(program, listing, buyer2, newAsset, offeringGroupAsset, buyer1, [vendor1.publicKey]);
     assert.ok(tx, "Transaction should be successful");
 
+    const vendorBalanceAfter = await connection.getBalance(vendor1.publicKey);
+    const expectedRoyalty = (price * offeringDetails.royaltyBasisPoints.toNumber()) / 10_000;
+    assert.equal(vendorBalanceAfter - vendorBalanceBefore, expectedRoyalty, "Creator should receive the royalty");
+  });
+
+  describe("Cancel Listing", () => {
+    let cancelAsset: Keypair, cancelListingPda;
+
+    before(async () => {
+      cancelAsset = Keypair.generate();
+      [cancelListingPda] = findListingPDA(cancelAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, cancelAsset);
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: null }, buyer1, cancelAsset, cancelListingPda);
+    });
+
+    it("should not let another wallet cancel the listing", async () => {
+      await expectError(cancelListing(program, cancelListingPda, buyer2, cancelAsset), "ConstraintSeeds");
+    });
+
+    it("should unlock the asset and close the listing", async () => {
+      const tx = await cancelListing(program, cancelListingPda, buyer1, cancelAsset);
+      assert.ok(tx, "Transaction should be successful");
+
+      const listingAccount = await program.account.listing.fetchNullable(cancelListingPda, "processed");
+      assert.isNull(listingAccount, "Listing should be closed");
+
+      const assetHeader = await fetchAssetHeader(program.provider.connection, cancelAsset.publicKey);
+      assert.isFalse(assetHeader.locked, "Asset should be unlocked");
+      assert.equal(assetHeader.delegateRoles, 0, "Delegate roles should be revoked");
+      assert.equal(assetHeader.owner.toBase58(), buyer1.publicKey.toBase58(), "Seller should still own the asset");
+    });
+  });
+
+  describe("Close Expired Listing", () => {
+    let activeAsset: Keypair, activeListing, expiredAsset: Keypair, expiredListing;
+
+    before(async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      activeAsset = Keypair.generate();
+      [activeListing] = findListingPDA(activeAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, activeAsset);
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: new BN(now + 3600) }, buyer1, activeAsset, activeListing);
+
+      expiredAsset = Keypair.generate();
+      [expiredListing] = findListingPDA(expiredAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, expiredAsset);
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: new BN(now - 60) }, buyer1, expiredAsset, expiredListing);
+    });
+
+    it("should fail to close a listing that has not expired", async () => {
+      await expectError(closeExpiredListing(program, activeListing, buyer2, buyer1, activeAsset), "ListingNotExpired");
+    });
+
+    it("should close an expired listing and pay the cranker a bounty", async () => {
+      const connection = program.provider.connection;
+      const listingRent = await connection.getBalance(expiredListing, "processed");
+      const sellerBalanceBefore = await connection.getBalance(buyer1.publicKey, "processed");
+
+      const tx = await closeExpiredListing(program, expiredListing, buyer2, buyer1, expiredAsset);
+      assert.ok(tx, "Transaction should be successful");
+
+      const listingAccount = await program.account.listing.fetchNullable(expiredListing, "processed");
+      assert.isNull(listingAccount, "Listing should be closed");
+
+      const sellerBalanceAfter = await connection.getBalance(buyer1.publicKey, "processed");
+      assert.equal(sellerBalanceAfter - sellerBalanceBefore, listingRent - EXPIRED_LISTING_BOUNTY, "Seller should get the rent minus the bounty");
+
+      const assetHeader = await fetchAssetHeader(connection, expiredAsset.publicKey);
+      assert.isFalse(assetHeader.locked, "Asset should be unlocked");
+      assert.equal(assetHeader.delegateRoles, 0, "Delegate roles should be revoked");
+    });
+  });
+
+  describe("Update Listing", () => {
+    let updateAsset: Keypair, updateListingPda;
+
+    before(async () => {
+      updateAsset = Keypair.generate();
+      [updateListingPda] = findListingPDA(updateAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, updateAsset);
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 600);
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt }, buyer1, updateAsset, updateListingPda);
+    });
+
+    it("should fail to bring the expiry forward", async () => {
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 300);
+      await expectError(updateListing(program, updateListingPda, buyer1, { solPrice: LAMPORTS_PER_SOL, expiresAt }), "ExpiryNotExtended");
+    });
+
+    it("should update the price and extend the expiry", async () => {
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 3600);
+      const tx = await updateListing(program, updateListingPda, buyer1, { solPrice: 3 * LAMPORTS_PER_SOL, expiresAt });
+      assert.ok(tx, "Transaction should be successful");
+
+      const listingAccount = await fetchListing(program, updateListingPda);
+      assert.equal(listingAccount.price.toNumber(), 3 * LAMPORTS_PER_SOL, "Price doesn't match");
+      assert.equal(listingAccount.expiresAt.toNumber(), expiresAt.toNumber(), "Expiry doesn't match");
+    });
+
+    it("should update the price alone and keep the expiry", async () => {
+      const { expiresAt } = await fetchListing(program, updateListingPda);
+      await updateListing(program, updateListingPda, buyer1, { solPrice: 2 * LAMPORTS_PER_SOL });
+
+      const listingAccount = await fetchListing(program, updateListingPda);
+      assert.equal(listingAccount.price.toNumber(), 2 * LAMPORTS_PER_SOL, "Price doesn't match");
+      assert.equal(listingAccount.expiresAt.toNumber(), expiresAt.toNumber(), "Expiry should be unchanged");
+    });
+
+    it("should add an expiry to a listing without one", async () => {
+      const asset = Keypair.generate();
+      const [listing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, asset);
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: null }, buyer1, asset, listing);
+
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 3600);
+      await updateListing(program, listing, buyer1, { expiresAt });
+
+      const listingAccount = await fetchListing(program, listing);
+      assert.equal(listingAccount.price.toNumber(), LAMPORTS_PER_SOL, "Price should be unchanged");
+      assert.equal(listingAccount.expiresAt.toNumber(), expiresAt.toNumber(), "Expiry doesn't match");
+    });
+
+    it("should fail to update a listing from another wallet", async () => {
+      await expectError(updateListing(program, updateListingPda, buyer2, { solPrice: 1 }), "ConstraintSeeds");
+    });
+  });
+
+  describe("Manage Service Offering", () => {
+    const managedOfferingDetails = { ...offeringDetails, offeringName: "Managed Offering", maxQuantity: 2 };
+    let managedOffering, managedGroupAsset;
+
+    before(async () => {
+      [managedOffering] = findServiceOfferingPDA(vendor2.publicKey, managedOfferingDetails.offeringName, program.programId);
+      [managedGroupAsset] = findOfferingGroupAssetPDA(managedOffering, program.programId);
+      await createServiceOffering(program, vendor2, managedOfferingDetails, managedOffering, managedGroupAsset);
+    });
+
+    it("should pause and resume sales", async () => {
+      await deactivateServiceOffering(program, vendor2, managedOfferingDetails.offeringName, managedOffering);
+      let serviceOfferingAccount = await fetchServiceOffering(program, managedOffering);
+      assert.isFalse(serviceOfferingAccount.active, "Service offering should be inactive");
+
+      await expectError(buyService(program, vendor2, managedOfferingDetails, managedOffering, managedGroupAsset, buyer1, Keypair.generate()), "ServiceNotActive");
+
+      await activateServiceOffering(program, vendor2, managedOfferingDetails.offeringName, managedOffering);
+      serviceOfferingAccount = await fetchServiceOffering(program, managedOffering);
+      assert.isTrue(serviceOfferingAccount.active, "Service offering should be active");
+    });
+
+    it("should update the price", async () => {
+      await updateServiceOfferingPrice(program, vendor2, managedOfferingDetails.offeringName, managedOffering, 2 * LAMPORTS_PER_SOL);
+
+      const serviceOfferingAccount = await fetchServiceOffering(program, managedOffering);
+      assert.equal(serviceOfferingAccount.solPrice.toNumber(), 2 * LAMPORTS_PER_SOL, "SOL price doesn't match");
+    });
+
+    it("should raise the max quantity of a sold out offering", async () => {
+      await buyService(program, vendor2, managedOfferingDetails, managedOffering, managedGroupAsset, buyer1, Keypair.generate());
+      await buyService(program, vendor2, managedOfferingDetails, managedOffering, managedGroupAsset, buyer1, Keypair.generate());
+
+      await updateServiceOfferingMaxQuantity(program, vendor2, managedOfferingDetails.offeringName, managedOffering, 3);
+
+      const serviceOfferingAccount = await fetchServiceOffering(program, managedOffering);
+      assert.equal(serviceOfferingAccount.maxQuantity.toNumber(), 3, "Max quantity doesn't match");
+    });
+
+    it("should fail to lower the max quantity below the number sold", async () => {
+      await expectError(updateServiceOfferingMaxQuantity(program, vendor2, managedOfferingDetails.offeringName, managedOffering, 1), "MaxQuantityBelowSold");
+    });
+  });
+
+  describe("Subscriptions", () => {
+    const periodSecs = 30 * 24 * 60 * 60;
+    const subscriptionOfferingDetails = {
+      ...offeringDetails,
+      offeringName: "Monthly Retainer",
+      serviceType: { subscription: { periodSecs: new BN(periodSecs), renewalPrice: new BN(LAMPORTS_PER_SOL / 2) } },
+    };
+    let subscriptionOffering, subscriptionGroupAsset, subscriptionAsset: Keypair, subscription;
+
+    before(async () => {
+      [subscriptionOffering] = findServiceOfferingPDA(vendor2.publicKey, subscriptionOfferingDetails.offeringName, program.programId);
+      [subscriptionGroupAsset] = findOfferingGroupAssetPDA(subscriptionOffering, program.programId);
+      subscriptionAsset = Keypair.generate();
+      [subscription] = findSubscriptionPDA(subscriptionAsset.publicKey, program.programId);
+      await createServiceOffering(program, vendor2, subscriptionOfferingDetails, subscriptionOffering, subscriptionGroupAsset);
+    });
+
+    it("should start a subscription when buying", async () => {
+      const tx = await buyService(program, vendor2, subscriptionOfferingDetails, subscriptionOffering, subscriptionGroupAsset, buyer1, subscriptionAsset, subscription);
+      assert.ok(tx, "Transaction should be successful");
+
+      const subscriptionAccount = await fetchSubscription(program, subscription);
+      assert.equal(subscriptionAccount.assetId.toBase58(), subscriptionAsset.publicKey.toBase58(), "Asset pubkey doesn't match");
+      assert.isAbove(subscriptionAccount.paidThrough.toNumber(), Math.floor(Date.now() / 1000), "Subscription should be paid through the future");
+    });
+
+    it("should report the holder's subscription as current", async () => {
+      assert.isTrue(await checkSubscription(program, buyer1.publicKey, subscriptionAsset.publicKey, subscription), "Holder should be current");
+      assert.isFalse(await checkSubscription(program, buyer2.publicKey, subscriptionAsset.publicKey, subscription), "Non-holder should not be current");
+    });
+
+    it("should extend the subscription by one period on renewal", async () => {
+      const { paidThrough } = await fetchSubscription(program, subscription);
+
+      const tx = await renewSubscription(program, buyer1, vendor2, subscriptionOfferingDetails.offeringName, subscriptionOffering, subscription);
+      assert.ok(tx, "Transaction should be successful");
+
+      const subscriptionAccount = await fetchSubscription(program, subscription);
+      assert.equal(subscriptionAccount.paidThrough.toNumber(), paidThrough.toNumber() + periodSecs, "Subscription should be extended by one period");
+    });
+
+    it("should fail to renew a one-time offering", async () => {
+      await expectError(renewSubscription(program, buyer1, vendor1, offeringDetails.offeringName, serviceOffering, subscription), "ConstraintHasOne");
+    });
+
+    it("should renew a tiered subscription at its tier's price", async () => {
+      const connection = program.provider.connection;
+      const tieredSubscriptionDetails = {
+        ...subscriptionOfferingDetails,
+        offeringName: "Tiered Retainer",
+        maxQuantity: 0,
+        tiers: [
+          { price: LAMPORTS_PER_SOL / 10, maxQuantity: 0 },
+          { price: LAMPORTS_PER_SOL / 4, maxQuantity: 0 },
+        ],
+      };
+      const [tieredOffering] = findServiceOfferingPDA(vendor2.publicKey, tieredSubscriptionDetails.offeringName, program.programId);
+      const [tieredGroupAsset] = findOfferingGroupAssetPDA(tieredOffering, program.programId);
+      await createServiceOffering(program, vendor2, tieredSubscriptionDetails, tieredOffering, tieredGroupAsset);
+
+      const asset = Keypair.generate();
+      const [tieredSubscription] = findSubscriptionPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, tieredSubscriptionDetails, tieredOffering, tieredGroupAsset, buyer1, asset, tieredSubscription, null, null, null, 1);
+
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      await renewSubscription(program, buyer1, vendor2, tieredSubscriptionDetails.offeringName, tieredOffering, tieredSubscription);
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, LAMPORTS_PER_SOL / 4, "Vendor should receive the tier price");
+    });
+  });
+
+  describe("SPL Token Payments", () => {
+    const tokenPrice = 25_000_000;
+
+    for (const [label, tokenProgram] of [["SPL Token", TOKEN_PROGRAM_ID], ["Token-2022", TOKEN_2022_PROGRAM_ID]] as const) {
+      it(`should buy a service priced in a ${label} mint`, async () => {
+        const connection = program.provider.connection;
+        const paymentMint = await setupPaymentMint(connection, vendor2, [buyer1.publicKey, vendor2.publicKey], 100_000_000, tokenProgram);
+        const tokenOfferingDetails = { ...offeringDetails, offeringName: `${label} Offering`, paymentMint, tokenPrice };
+
+        const [tokenOffering] = findServiceOfferingPDA(vendor2.publicKey, tokenOfferingDetails.offeringName, program.programId);
+        const [tokenGroupAsset] = findOfferingGroupAssetPDA(tokenOffering, program.programId);
+        await createServiceOffering(program, vendor2, tokenOfferingDetails, tokenOffering, tokenGroupAsset);
+
+        const payment = {
+          paymentMint,
+          payerTokenAccount: getAssociatedTokenAddressSync(paymentMint, buyer1.publicKey, false, tokenProgram),
+          receiverTokenAccount: getAssociatedTokenAddressSync(paymentMint, vendor2.publicKey, false, tokenProgram),
+          tokenProgram,
+        };
+        const vendorLamportsBefore = await connection.getBalance(vendor2.publicKey, "processed");
+
+        const tx = await buyService(program, vendor2, tokenOfferingDetails, tokenOffering, tokenGroupAsset, buyer1, Keypair.generate(), null, payment);
+        assert.ok(tx, "Transaction should be successful");
+
+        const vendorTokenAccount = await getAccount(connection, payment.receiverTokenAccount, "processed", tokenProgram);
+        const buyerTokenAccount = await getAccount(connection, payment.payerTokenAccount, "processed", tokenProgram);
+        assert.equal(Number(vendorTokenAccount.amount), tokenPrice, "Vendor should receive the token price");
+        assert.equal(Number(buyerTokenAccount.amount), 100_000_000 - tokenPrice, "Buyer should pay the token price");
+        assert.equal(await connection.getBalance(vendor2.publicKey, "processed"), vendorLamportsBefore, "Vendor should not receive SOL");
+      });
+    }
+
+    it("should fail to buy a token-priced offering without token accounts", async () => {
+      const [tokenOffering] = findServiceOfferingPDA(vendor2.publicKey, "SPL Token Offering", program.programId);
+      const [tokenGroupAsset] = findOfferingGroupAssetPDA(tokenOffering, program.programId);
+
+      await expectError(buyService(program, vendor2, { ...offeringDetails, offeringName: "SPL Token Offering" }, tokenOffering, tokenGroupAsset, buyer1, Keypair.generate()), "MissingPaymentAccounts");
+    });
+
+    it("should reprice a token-priced offering in its mint", async () => {
+      const [tokenOffering] = findServiceOfferingPDA(vendor2.publicKey, "SPL Token Offering", program.programId);
+
+      await expectError(updateServiceOfferingPrice(program, vendor2, "SPL Token Offering", tokenOffering, LAMPORTS_PER_SOL), "PriceNotUpdatable");
+      await updateServiceOfferingTokenPrice(program, vendor2, "SPL Token Offering", tokenOffering, 2 * tokenPrice);
+
+      const offeringAccount = await fetchServiceOffering(program, tokenOffering);
+      assert.equal(offeringAccount.tokenPrice.toNumber(), 2 * tokenPrice, "Token price doesn't match");
+    });
+  });
+
+  describe("SPL Token Listings", () => {
+    const listingPrice = 50_000_000;
+
+    it("should settle a token listing with the seller and creators", async () => {
+      const connection = program.provider.connection;
+      const paymentMint = await setupPaymentMint(connection, vendor2, [buyer2.publicKey, buyer1.publicKey, vendor1.publicKey], 100_000_000, TOKEN_PROGRAM_ID);
+      const ata = (owner) => getAssociatedTokenAddressSync(paymentMint, owner, false, TOKEN_PROGRAM_ID);
+
+      const tokenAsset = Keypair.generate();
+      const [tokenListing] = findListingPDA(tokenAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, tokenAsset);
+      await listAsset(program, { solPrice: listingPrice, expiresAt: null, paymentMint }, buyer1, tokenAsset, tokenListing);
+
+      const payment = {
+        paymentMint,
+        payerTokenAccount: ata(buyer2.publicKey),
+        receiverTokenAccount: ata(buyer1.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+      const tx = await buyListing(program, tokenListing, buyer2, tokenAsset, offeringGroupAsset, buyer1, [ata(vendor1.publicKey)], payment);
+      assert.ok(tx, "Transaction should be successful");
+
+      const royalty = (listingPrice * offeringDetails.royaltyBasisPoints.toNumber()) / 10_000;
+      const sellerTokenAccount = await getAccount(connection, ata(buyer1.publicKey), "processed", TOKEN_PROGRAM_ID);
+      const creatorTokenAccount = await getAccount(connection, ata(vendor1.publicKey), "processed", TOKEN_PROGRAM_ID);
+      assert.equal(Number(sellerTokenAccount.amount), 100_000_000 + listingPrice - royalty, "Seller should receive the price minus royalties");
+      assert.equal(Number(creatorTokenAccount.amount), 100_000_000 + royalty, "Creator should receive the royalty");
+
+      const assetHeader = await fetchAssetHeader(connection, tokenAsset.publicKey);
+      assert.equal(assetHeader.owner.toBase58(), buyer2.publicKey.toBase58(), "Buyer should own the asset");
+    });
+  });
+
+  describe("Protocol Fees", () => {
+    const primaryFeeBps = 250;
+    const secondaryFeeBps = 500;
+
+    before(async () => {
+      await updateMarketplaceConfig(program, admin, admin.publicKey, treasury.publicKey, admin.publicKey, primaryFeeBps, secondaryFeeBps);
+    });
+
+    after(async () => {
+      await updateMarketplaceConfig(program, admin, admin.publicKey, treasury.publicKey, admin.publicKey, 0, 0);
+    });
+
+    it("should fail to update the config from a non-admin", async () => {
+      await expectError(updateMarketplaceConfig(program, buyer1, buyer1.publicKey, buyer1.publicKey, buyer1.publicKey, 0, 0), "ConstraintHasOne");
+    });
+
+    it("should fail to set a fee above the maximum", async () => {
+      await expectError(updateMarketplaceConfig(program, admin, admin.publicKey, treasury.publicKey, admin.publicKey, MAX_PROTOCOL_FEE_BPS + 1, 0), "FeeTooHigh");
+    });
+
+    it("should take the primary fee from a service purchase", async () => {
+      const connection = program.provider.connection;
+      const treasuryBefore = await connection.getBalance(treasury.publicKey, "processed");
+      const vendorBefore = await connection.getBalance(vendor1.publicKey, "processed");
+
+      const feeAsset = Keypair.generate();
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, feeAsset);
+
+      const fee = (offeringDetails.solPrice * primaryFeeBps) / 10_000;
+      assert.equal(await connection.getBalance(treasury.publicKey, "processed") - treasuryBefore, fee, "Treasury should receive the primary fee");
+      assert.equal(await connection.getBalance(vendor1.publicKey, "processed") - vendorBefore, offeringDetails.solPrice - fee, "Vendor should receive the price minus the fee");
+    });
+
+    it("should take the primary fee from a subscription renewal", async () => {
+      const connection = program.provider.connection;
+      const renewalPrice = LAMPORTS_PER_SOL / 2;
+      const subscriptionDetails = { ...offeringDetails, offeringName: "Monthly Retainer" };
+      const [subscriptionOffering] = findServiceOfferingPDA(vendor2.publicKey, subscriptionDetails.offeringName, program.programId);
+      const [subscriptionGroupAsset] = findOfferingGroupAssetPDA(subscriptionOffering, program.programId);
+      const asset = Keypair.generate();
+      const [subscription] = findSubscriptionPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, subscriptionDetails, subscriptionOffering, subscriptionGroupAsset, buyer1, asset, subscription);
+
+      const treasuryBefore = await connection.getBalance(treasury.publicKey, "processed");
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      await renewSubscription(program, buyer1, vendor2, subscriptionDetails.offeringName, subscriptionOffering, subscription);
+
+      const fee = (renewalPrice * primaryFeeBps) / 10_000;
+      assert.equal(await connection.getBalance(treasury.publicKey, "processed") - treasuryBefore, fee, "Treasury should receive the primary fee");
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, renewalPrice - fee, "Vendor should receive the renewal price minus the fee");
+    });
+
+    it("should take the secondary fee from a listing purchase", async () => {
+      const connection = program.provider.connection;
+      const price = 2 * LAMPORTS_PER_SOL;
+      const feeAsset = Keypair.generate();
+      const [feeListing] = findListingPDA(feeAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, feeAsset);
+      await listAsset(program, { solPrice: price, expiresAt: null }, buyer1, feeAsset, feeListing);
+
+      const treasuryBefore = await connection.getBalance(treasury.publicKey, "processed");
+      await buyListing(program, feeListing, buyer2, feeAsset, offeringGroupAsset, buyer1, [vendor1.publicKey]);
+
+      const fee = (price * secondaryFeeBps) / 10_000;
+      assert.equal(await connection.getBalance(treasury.publicKey, "processed") - treasuryBefore, fee, "Treasury should receive the secondary fee");
+    });
+  });
+
+  describe("Escrowed Orders", () => {
+    const escrowOfferingDetails = {
+      ...offeringDetails,
+      offeringName: "Escrowed Offering",
+      escrowTimeoutSecs: 1,
+    };
+    let escrowOffering, escrowGroupAsset;
+
+    before(async () => {
+      [escrowOffering] = findServiceOfferingPDA(vendor2.publicKey, escrowOfferingDetails.offeringName, program.programId);
+      [escrowGroupAsset] = findOfferingGroupAssetPDA(escrowOffering, program.programId);
+      await createServiceOffering(program, vendor2, escrowOfferingDetails, escrowOffering, escrowGroupAsset);
+    });
+
+    async function buyEscrowed(asset: Keypair) {
+      const [orderEscrow] = findOrderEscrowPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, escrowOfferingDetails, escrowOffering, escrowGroupAsset, buyer1, asset, null, null, orderEscrow);
+      return orderEscrow;
+    }
+
+    it("should hold the payment in escrow when buying", async () => {
+      const connection = program.provider.connection;
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+
+      const orderEscrow = await buyEscrowed(Keypair.generate());
+
+      const escrowAccount = await fetchOrderEscrow(program, orderEscrow);
+      assert.deepEqual(escrowAccount.status, { paid: {} }, "Order should be paid");
+      assert.equal(escrowAccount.amount.toNumber(), escrowOfferingDetails.solPrice, "Escrow should hold the price");
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed"), vendorBefore, "Vendor should not be paid yet");
+    });
+
+    it("should fail to buy an escrowed offering without an order escrow", async () => {
+      await expectError(buyService(program, vendor2, escrowOfferingDetails, escrowOffering, escrowGroupAsset, buyer1, Keypair.generate()), "MissingOrderEscrow");
+    });
+
+    it("should pay the vendor when the buyer confirms fulfillment", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const orderEscrow = await buyEscrowed(asset);
+
+      await markFulfilled(program, vendor2, asset.publicKey);
+      const escrowAccount = await fetchOrderEscrow(program, orderEscrow);
+      assert.deepEqual(escrowAccount.status, { fulfilled: {} }, "Order should be fulfilled");
+
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      const tx = await confirmFulfillment(program, buyer1, vendor2, asset.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, escrowOfferingDetails.solPrice, "Vendor should receive the escrowed price");
+      assert.isNull(await connection.getAccountInfo(orderEscrow, "processed"), "Order escrow should be closed");
+    });
+
+    it("should fail to release an unfulfilled order", async () => {
+      const asset = Keypair.generate();
+      await buyEscrowed(asset);
+
+      await expectError(releaseEscrow(program, buyer2, buyer1.publicKey, vendor2.publicKey, asset.publicKey), "ReleaseTimeoutNotReached");
+    });
+
+    it("should let anyone release a fulfilled order after the timeout", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const orderEscrow = await buyEscrowed(asset);
+      await markFulfilled(program, vendor2, asset.publicKey);
+
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      const tx = await releaseEscrow(program, buyer2, buyer1.publicKey, vendor2.publicKey, asset.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, escrowOfferingDetails.solPrice, "Vendor should receive the escrowed price");
+      assert.isNull(await connection.getAccountInfo(orderEscrow, "processed"), "Order escrow should be closed");
+    });
+
+    it("should refund the buyer and burn the asset before fulfillment", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const orderEscrow = await buyEscrowed(asset);
+      const { numSold } = await fetchServiceOffering(program, escrowOffering);
+
+      const tx = await requestRefund(program, buyer1, escrowOffering, escrowGroupAsset, asset.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      const offeringAccount = await fetchServiceOffering(program, escrowOffering);
+      assert.equal(offeringAccount.numSold.toNumber(), numSold.toNumber() - 1, "Refund should free up a unit");
+      assert.isNull(await connection.getAccountInfo(orderEscrow, "processed"), "Order escrow should be closed");
+      assert.isNull(await connection.getAccountInfo(asset.publicKey, "processed"), "Asset should be burned");
+    });
+
+    it("should fail to refund a fulfilled order", async () => {
+      const asset = Keypair.generate();
+      await buyEscrowed(asset);
+      await markFulfilled(program, vendor2, asset.publicKey);
+
+      await expectError(requestRefund(program, buyer1, escrowOffering, escrowGroupAsset, asset.publicKey), "InvalidOrderStatus");
+    });
+
+    it("should fail to refund a redeemed order", async () => {
+      const asset = Keypair.generate();
+      await buyEscrowed(asset);
+      await redeemService(program, buyer1, vendor2, escrowOfferingDetails.offeringName, escrowOffering, escrowGroupAsset, asset.publicKey);
+
+      await expectError(requestRefund(program, buyer1, escrowOffering, escrowGroupAsset, asset.publicKey), "AlreadyRedeemed");
+    });
+
+    it("should fail to refund an order after a use is consumed", async () => {
+      const usesEscrowDetails = { ...escrowOfferingDetails, offeringName: "Escrowed Coaching", usesPerAsset: 2 };
+      const [usesEscrowOffering] = findServiceOfferingPDA(vendor2.publicKey, usesEscrowDetails.offeringName, program.programId);
+      const [usesEscrowGroupAsset] = findOfferingGroupAssetPDA(usesEscrowOffering, program.programId);
+      await createServiceOffering(program, vendor2, usesEscrowDetails, usesEscrowOffering, usesEscrowGroupAsset);
+
+      const asset = Keypair.generate();
+      const [orderEscrow] = findOrderEscrowPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, usesEscrowDetails, usesEscrowOffering, usesEscrowGroupAsset, buyer1, asset, null, null, orderEscrow);
+      await consumeUse(program, vendor2, usesEscrowDetails.offeringName, usesEscrowOffering, asset.publicKey);
+
+      await expectError(requestRefund(program, buyer1, usesEscrowOffering, usesEscrowGroupAsset, asset.publicKey), "UsesAlreadyConsumed");
+    });
+
+    it("should give back the purchase count and coupon redemption on refund", async () => {
+      const code = "ESCROW-TENTH";
+      const codeHash = hashCouponCode(code);
+      const [coupon] = findCouponPDA(escrowOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, escrowOfferingDetails.offeringName, escrowOffering, coupon, { codeHash, discount: { percentage: { bps: 1_000 } } });
+
+      const asset = Keypair.generate();
+      const [orderEscrow] = findOrderEscrowPDA(asset.publicKey, program.programId);
+      const [purchaseCounter] = findPurchaseCounterPDA(escrowOffering, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, escrowOfferingDetails, escrowOffering, escrowGroupAsset, buyer1, asset, null, null, orderEscrow, { purchaseCounter }, null, { coupon, code });
+      const { purchased } = await program.account.purchaseCounter.fetch(purchaseCounter, "processed");
+
+      await expectError(requestRefund(program, buyer1, escrowOffering, escrowGroupAsset, asset.publicKey), "MissingPurchaseCounter");
+      await requestRefund(program, buyer1, escrowOffering, escrowGroupAsset, asset.publicKey, purchaseCounter, coupon);
+
+      const counterAccount = await program.account.purchaseCounter.fetch(purchaseCounter, "processed");
+      assert.equal(counterAccount.purchased.toNumber(), purchased.toNumber() - 1, "Purchase should be taken back");
+      const couponAccount = await fetchCoupon(program, coupon);
+      assert.equal(couponAccount.redemptions.toNumber(), 0, "Redemption should be given back");
+    });
+  });
+
+  describe("Disputes", () => {
+    const disputeOfferingDetails = {
+      ...offeringDetails,
+      offeringName: "Arbitrated Offering",
+      escrowTimeoutSecs: 60,
+    };
+    let disputeOffering, disputeGroupAsset, arbiter: Keypair;
+
+    before(async () => {
+      // A per-offering arbiter overrides the marketplace arbiter (admin)
+      arbiter = Keypair.generate();
+      [disputeOffering] = findServiceOfferingPDA(vendor2.publicKey, disputeOfferingDetails.offeringName, program.programId);
+      [disputeGroupAsset] = findOfferingGroupAssetPDA(disputeOffering, program.programId);
+      await createServiceOffering(program, vendor2, { ...disputeOfferingDetails, arbiter: arbiter.publicKey }, disputeOffering, disputeGroupAsset);
+    });
+
+    async function buyDisputed(asset: Keypair) {
+      const [orderEscrow] = findOrderEscrowPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, disputeOfferingDetails, disputeOffering, disputeGroupAsset, buyer1, asset, null, null, orderEscrow);
+      await openDispute(program, buyer1, asset.publicKey);
+      return orderEscrow;
+    }
+
+    it("should fail to create an offering arbitrated by its vendor", async () => {
+      const selfArbitratedDetails = { ...disputeOfferingDetails, offeringName: "Self Arbitrated Offering", arbiter: vendor2.publicKey };
+      const [selfArbitratedOffering] = findServiceOfferingPDA(vendor2.publicKey, selfArbitratedDetails.offeringName, program.programId);
+      const [selfArbitratedGroupAsset] = findOfferingGroupAssetPDA(selfArbitratedOffering, program.programId);
+
+      await expectError(createServiceOffering(program, vendor2, selfArbitratedDetails, selfArbitratedOffering, selfArbitratedGroupAsset), "VendorCannotArbitrate");
+    });
+
+    it("should assign the offering arbiter to the order", async () => {
+      const orderEscrow = await buyDisputed(Keypair.generate());
+
+      const escrowAccount = await fetchOrderEscrow(program, orderEscrow);
+      assert.equal(escrowAccount.arbiter.toBase58(), arbiter.publicKey.toBase58(), "Arbiter doesn't match");
+      assert.deepEqual(escrowAccount.status, { disputed: {} }, "Order should be disputed");
+      assert.isNotNull(escrowAccount.disputedAt, "Dispute timestamp should be recorded");
+    });
+
+    it("should fail to open a dispute from a third party", async () => {
+      const asset = Keypair.generate();
+      const [orderEscrow] = findOrderEscrowPDA(asset.publicKey, program.programId);
+      await buyService(program, vendor2, disputeOfferingDetails, disputeOffering, disputeGroupAsset, buyer1, asset, null, null, orderEscrow);
+
+      await expectError(openDispute(program, buyer2, asset.publicKey), "NotOrderParty");
+    });
+
+    it("should record evidence from both parties", async () => {
+      const asset = Keypair.generate();
+      const orderEscrow = await buyDisputed(asset);
+
+      await submitEvidenceUri(program, buyer1, asset.publicKey, "https://test.com/buyer-evidence.pdf");
+      await submitEvidenceUri(program, vendor2, asset.publicKey, "https://test.com/vendor-evidence.pdf");
+
+      const escrowAccount = await fetchOrderEscrow(program, orderEscrow);
+      assert.equal(escrowAccount.buyerEvidenceUri, "https://test.com/buyer-evidence.pdf", "Buyer evidence doesn't match");
+      assert.equal(escrowAccount.vendorEvidenceUri, "https://test.com/vendor-evidence.pdf", "Vendor evidence doesn't match");
+    });
+
+    it("should fail to confirm a disputed order", async () => {
+      const asset = Keypair.generate();
+      await buyDisputed(asset);
+
+      await expectError(confirmFulfillment(program, buyer1, vendor2, asset.publicKey), "InvalidOrderStatus");
+    });
+
+    it("should fail to resolve a dispute from another arbiter", async () => {
+      const asset = Keypair.generate();
+      await buyDisputed(asset);
+
+      await expectError(resolveDispute(program, admin, buyer1.publicKey, vendor2.publicKey, asset.publicKey, 5_000), "InvalidArbiter");
+    });
+
+    it("should fail to apply the default split before the dispute deadline", async () => {
+      const asset = Keypair.generate();
+      await buyDisputed(asset);
+
+      await expectError(resolveExpiredDispute(program, buyer2, buyer1.publicKey, vendor2.publicKey, asset.publicKey), "DisputeDeadlineNotReached");
+    });
+
+    it("should split the escrow between buyer and vendor", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const orderEscrow = await buyDisputed(asset);
+      const { amount } = await fetchOrderEscrow(program, orderEscrow);
+      const escrowRent = await connection.getBalance(orderEscrow, "processed") - amount.toNumber();
+
+      const buyerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+
+      const tx = await resolveDispute(program, arbiter, buyer1.publicKey, vendor2.publicKey, asset.publicKey, 2_500);
+      assert.ok(tx, "Transaction should be successful");
+
+      const buyerShare = disputeOfferingDetails.solPrice / 4;
+      assert.equal(await connection.getBalance(buyer1.publicKey, "processed") - buyerBefore, buyerShare + escrowRent, "Buyer should receive their share and the rent");
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, disputeOfferingDetails.solPrice - buyerShare, "Vendor should receive the rest");
+      assert.isNull(await connection.getAccountInfo(orderEscrow, "processed"), "Order escrow should be closed");
+    });
+  });
+
+  describe("Redeem Service", () => {
+    const attributeOfferingDetails = { ...offeringDetails, offeringName: "Redeemable Offering" };
+    const burnOfferingDetails = { ...offeringDetails, offeringName: "Burnable Offering", redeemMode: { burn: {} } };
+    let attributeOffering, attributeGroupAsset, burnOffering, burnGroupAsset;
+
+    before(async () => {
+      [attributeOffering] = findServiceOfferingPDA(vendor2.publicKey, attributeOfferingDetails.offeringName, program.programId);
+      [attributeGroupAsset] = findOfferingGroupAssetPDA(attributeOffering, program.programId);
+      [burnOffering] = findServiceOfferingPDA(vendor2.publicKey, burnOfferingDetails.offeringName, program.programId);
+      [burnGroupAsset] = findOfferingGroupAssetPDA(burnOffering, program.programId);
+      await createServiceOffering(program, vendor2, attributeOfferingDetails, attributeOffering, attributeGroupAsset);
+      await createServiceOffering(program, vendor2, burnOfferingDetails, burnOffering, burnGroupAsset);
+    });
+
+    it("should mark the asset as redeemed and refuse to list it", async () => {
+      const asset = Keypair.generate();
+      const [redeemedListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, attributeOfferingDetails, attributeOffering, attributeGroupAsset, buyer1, asset);
+
+      const tx = await redeemService(program, buyer1, vendor2, attributeOfferingDetails.offeringName, attributeOffering, attributeGroupAsset, asset.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      const assetHeader = await fetchAssetHeader(program.provider.connection, asset.publicKey);
+      assert.equal(assetHeader.owner.toBase58(), buyer1.publicKey.toBase58(), "Holder should keep the asset");
+
+      await expectError(listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: null }, buyer1, asset, redeemedListing), "AssetIsRedeemed");
+    });
+
+    it("should fail to redeem an asset twice", async () => {
+      const asset = Keypair.generate();
+      await buyService(program, vendor2, attributeOfferingDetails, attributeOffering, attributeGroupAsset, buyer1, asset);
+      await redeemService(program, buyer1, vendor2, attributeOfferingDetails.offeringName, attributeOffering, attributeGroupAsset, asset.publicKey);
+
+      await expectError(redeemService(program, buyer1, vendor2, attributeOfferingDetails.offeringName, attributeOffering, attributeGroupAsset, asset.publicKey), "AlreadyRedeemed");
+    });
+
+    it("should fail to redeem without the holder's signature", async () => {
+      const asset = Keypair.generate();
+      await buyService(program, vendor2, attributeOfferingDetails, attributeOffering, attributeGroupAsset, buyer1, asset);
+
+      await expectError(redeemService(program, buyer2, vendor2, attributeOfferingDetails.offeringName, attributeOffering, attributeGroupAsset, asset.publicKey), "RequireKeysEqViolated");
+    });
+
+    it("should burn the asset for burn-on-redeem offerings", async () => {
+      const asset = Keypair.generate();
+      await buyService(program, vendor2, burnOfferingDetails, burnOffering, burnGroupAsset, buyer1, asset);
+
+      const tx = await redeemService(program, buyer1, vendor2, burnOfferingDetails.offeringName, burnOffering, burnGroupAsset, asset.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      assert.isNull(await program.provider.connection.getAccountInfo(asset.publicKey, "processed"), "Asset should be burned");
+    });
+  });
+
+  describe("Multi-Use Services", () => {
+    const usesOfferingDetails = { ...offeringDetails, offeringName: "Coaching Package", usesPerAsset: 2 };
+    let usesOffering, usesGroupAsset;
+
+    before(async () => {
+      [usesOffering] = findServiceOfferingPDA(vendor2.publicKey, usesOfferingDetails.offeringName, program.programId);
+      [usesGroupAsset] = findOfferingGroupAssetPDA(usesOffering, program.programId);
+      await createServiceOffering(program, vendor2, usesOfferingDetails, usesOffering, usesGroupAsset);
+    });
+
+    it("should block uses once the counter reaches zero", async () => {
+      const asset = Keypair.generate();
+      await buyService(program, vendor2, usesOfferingDetails, usesOffering, usesGroupAsset, buyer1, asset);
+
+      await consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey);
+      await consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey);
+
+      await expectError(consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey), "NoUsesRemaining");
+    });
+
+    it("should fail to consume a use of a single-use service", async () => {
+      const asset = Keypair.generate();
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, asset);
+
+      await expectError(consumeUse(program, vendor1, offeringDetails.offeringName, serviceOffering, asset.publicKey), "NotMultiUse");
+    });
+
+    it("should show and preserve the remaining uses on a listing", async () => {
+      const asset = Keypair.generate();
+      const [usesListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, usesOfferingDetails, usesOffering, usesGroupAsset, buyer1, asset);
+      await consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey);
+
+      await listAsset(program, { solPrice: LAMPORTS_PER_SOL, expiresAt: null }, buyer1, asset, usesListing);
+      const listingAccount = await fetchListing(program, usesListing);
+      assert.equal(listingAccount.remainingUses.toNumber(), 1, "Listing should show the remaining uses");
+
+      await expectError(consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey), "AssetIsLocked");
+    });
+
+    it("should fail to accept an offer after a use is consumed", async () => {
+      const asset = Keypair.generate();
+      const [offer] = findOfferPDA(asset.publicKey, buyer2.publicKey, program.programId);
+      await buyService(program, vendor2, usesOfferingDetails, usesOffering, usesGroupAsset, buyer1, asset);
+      await makeOffer(program, buyer2, asset.publicKey, LAMPORTS_PER_SOL / 2);
+
+      const offerAccount = await fetchOffer(program, offer);
+      assert.equal(offerAccount.remainingUses.toNumber(), 2, "Offer should record the remaining uses");
+
+      await consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey);
+      await expectError(acceptOffer(program, buyer1, buyer2.publicKey, asset.publicKey, usesGroupAsset, [vendor2.publicKey]), "InsufficientUses");
+    });
+  });
+
+  describe("Offers", () => {
+    const offerPrice = LAMPORTS_PER_SOL / 2;
+    let offerAsset: Keypair;
+
+    before(async () => {
+      offerAsset = Keypair.generate();
+      await buyService(program, vendor1, offeringDetails, serviceOffering, offeringGroupAsset, buyer1, offerAsset);
+    });
+
+    it("should escrow the offered price", async () => {
+      const [offer] = findOfferPDA(offerAsset.publicKey, buyer2.publicKey, program.programId);
+
+      const tx = await makeOffer(program, buyer2, offerAsset.publicKey, offerPrice);
+      assert.ok(tx, "Transaction should be successful");
+
+      const offerAccount = await fetchOffer(program, offer);
+      assert.equal(offerAccount.price.toNumber(), offerPrice, "Price doesn't match");
+      assert.isAtLeast(await program.provider.connection.getBalance(offer, "processed"), offerPrice, "Offer should hold the price");
+    });
+
+    it("should fail to accept an offer from a non-owner", async () => {
+      await expectError(acceptOffer(program, vendor2, buyer2.publicKey, offerAsset.publicKey, offeringGroupAsset, [vendor1.publicKey]), "NotAssetOwner");
+    });
+
+    it("should refund the bidder when cancelling", async () => {
+      const connection = program.provider.connection;
+      const [offer] = findOfferPDA(offerAsset.publicKey, buyer2.publicKey, program.programId);
+      const offerBalance = await connection.getBalance(offer, "processed");
+      const bidderBefore = await connection.getBalance(buyer2.publicKey, "processed");
+
+      await cancelOffer(program, buyer2, offerAsset.publicKey);
+
+      assert.equal(await connection.getBalance(buyer2.publicKey, "processed") - bidderBefore, offerBalance, "Bidder should get the escrow back");
+      assert.isNull(await connection.getAccountInfo(offer, "processed"), "Offer should be closed");
+    });
+
+    it("should transfer the asset and pay the seller and royalties on acceptance", async () => {
+      const connection = program.provider.connection;
+      const [offer] = findOfferPDA(offerAsset.publicKey, buyer2.publicKey, program.programId);
+      await makeOffer(program, buyer2, offerAsset.publicKey, offerPrice);
+
+      const sellerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+      const creatorBefore = await connection.getBalance(vendor1.publicKey, "processed");
+
+      const tx = await acceptOffer(program, buyer1, buyer2.publicKey, offerAsset.publicKey, offeringGroupAsset, [vendor1.publicKey]);
+      assert.ok(tx, "Transaction should be successful");
+
+      const royalty = (offerPrice * offeringDetails.royaltyBasisPoints.toNumber()) / 10_000;
+      assert.equal(await connection.getBalance(vendor1.publicKey, "processed") - creatorBefore, royalty, "Creator should receive the royalty");
+      assert.equal(await connection.getBalance(buyer1.publicKey, "processed") - sellerBefore, offerPrice - royalty, "Seller should receive the rest");
+
+      const assetHeader = await fetchAssetHeader(connection, offerAsset.publicKey);
+      assert.equal(assetHeader.owner.toBase58(), buyer2.publicKey.toBase58(), "Bidder should own the asset");
+      assert.isNull(await connection.getAccountInfo(offer, "processed"), "Offer should be closed");
+    });
+  });
+
+  describe("Collection Bids", () => {
+    const bidPrice = LAMPORTS_PER_SOL / 4;
+    const bidOfferingDetails = { ...offeringDetails, offeringName: "Bid Offering" };
+    let bidOffering, bidGroupAsset, collectionBid;
+
+    before(async () => {
+      [bidOffering] = findServiceOfferingPDA(vendor2.publicKey, bidOfferingDetails.offeringName, program.programId);
+      [bidGroupAsset] = findOfferingGroupAssetPDA(bidOffering, program.programId);
+      [collectionBid] = findCollectionBidPDA(bidGroupAsset, buyer2.publicKey, program.programId);
+      await createServiceOffering(program, vendor2, bidOfferingDetails, bidOffering, bidGroupAsset);
+    });
+
+    it("should escrow the price of every unit", async () => {
+      const tx = await placeCollectionBid(program, buyer2, bidGroupAsset, bidPrice, 2);
+      assert.ok(tx, "Transaction should be successful");
+
+      const bidAccount = await fetchCollectionBid(program, collectionBid);
+      assert.equal(bidAccount.quantity.toNumber(), 2, "Quantity doesn't match");
+      assert.isAtLeast(await program.provider.connection.getBalance(collectionBid, "processed"), 2 * bidPrice, "Bid should hold the price of every unit");
+    });
+
+    it("should fail to fill with an asset from another group", async () => {
+      const otherAsset = Keypair.generate();
+      const [otherOffering] = findServiceOfferingPDA(vendor2.publicKey, "Redeemable Offering", program.programId);
+      const [otherGroupAsset] = findOfferingGroupAssetPDA(otherOffering, program.programId);
+      await buyService(program, vendor2, { ...offeringDetails, offeringName: "Redeemable Offering" }, otherOffering, otherGroupAsset, buyer1, otherAsset);
+
+      await expectError(fillCollectionBid(program, buyer1, buyer2.publicKey, otherAsset.publicKey, bidGroupAsset, [vendor2.publicKey]), "InvalidGroup");
+    });
+
+    it("should transfer assets and decrement the quantity on each fill", async () => {
+      const connection = program.provider.connection;
+
+      for (const remaining of [1, 0]) {
+        const asset = Keypair.generate();
+        await buyService(program, vendor2, bidOfferingDetails, bidOffering, bidGroupAsset, buyer1, asset);
+
+        const sellerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+        const tx = await fillCollectionBid(program, buyer1, buyer2.publicKey, asset.publicKey, bidGroupAsset, [vendor2.publicKey]);
+        assert.ok(tx, "Transaction should be successful");
+
+        const royalty = (bidPrice * bidOfferingDetails.royaltyBasisPoints.toNumber()) / 10_000;
+        assert.equal(await connection.getBalance(buyer1.publicKey, "processed") - sellerBefore, bidPrice - royalty, "Seller should receive the price minus royalties");
+
+        const assetHeader = await fetchAssetHeader(connection, asset.publicKey);
+        assert.equal(assetHeader.owner.toBase58(), buyer2.publicKey.toBase58(), "Bidder should own the asset");
+
+        if (remaining > 0) {
+          const bidAccount = await fetchCollectionBid(program, collectionBid);
+          assert.equal(bidAccount.quantity.toNumber(), remaining, "Quantity should be decremented");
+        }
+      }
+
+      assert.isNull(await connection.getAccountInfo(collectionBid, "processed"), "Filled bid should be closed");
+    });
+
+    it("should refund the unfilled quantity when cancelling", async () => {
+      const connection = program.provider.connection;
+      await placeCollectionBid(program, buyer2, bidGroupAsset, bidPrice, 3);
+      const bidBalance = await connection.getBalance(collectionBid, "processed");
+      const bidderBefore = await connection.getBalance(buyer2.publicKey, "processed");
+
+      await cancelCollectionBid(program, buyer2, bidGroupAsset);
+
+      assert.equal(await connection.getBalance(buyer2.publicKey, "processed") - bidderBefore, bidBalance, "Bidder should get the escrow back");
+      assert.isNull(await connection.getAccountInfo(collectionBid, "processed"), "Bid should be closed");
+    });
+
+    it("should refuse assets with too few uses or of another tier", async () => {
+      const usesOfferingDetails = { ...offeringDetails, offeringName: "Coaching Package", usesPerAsset: 2 };
+      const [usesOffering] = findServiceOfferingPDA(vendor2.publicKey, usesOfferingDetails.offeringName, program.programId);
+      const [usesGroupAsset] = findOfferingGroupAssetPDA(usesOffering, program.programId);
+      const asset = Keypair.generate();
+      await buyService(program, vendor2, usesOfferingDetails, usesOffering, usesGroupAsset, buyer1, asset);
+      await consumeUse(program, vendor2, usesOfferingDetails.offeringName, usesOffering, asset.publicKey);
+
+      await placeCollectionBid(program, buyer2, usesGroupAsset, bidPrice, 1, null, 2);
+      await expectError(fillCollectionBid(program, buyer1, buyer2.publicKey, asset.publicKey, usesGroupAsset, [vendor2.publicKey]), "InsufficientUses");
+      await cancelCollectionBid(program, buyer2, usesGroupAsset);
+
+      await placeCollectionBid(program, buyer2, usesGroupAsset, bidPrice, 1, null, 1, 0);
+      await expectError(fillCollectionBid(program, buyer1, buyer2.publicKey, asset.publicKey, usesGroupAsset, [vendor2.publicKey]), "TierMismatch");
+      await cancelCollectionBid(program, buyer2, usesGroupAsset);
+    });
+  });
+
+  describe("Auctions", () => {
+    const auctionOfferingDetails = { ...offeringDetails, offeringName: "Auction Offering", maxQuantity: 3 };
+    const reservePrice = LAMPORTS_PER_SOL;
+    const minBidIncrement = LAMPORTS_PER_SOL / 10;
+    let auctionOffering, auctionGroupAsset, auctionAsset: Keypair, auction;
+
+    before(async () => {
+      [auctionOffering] = findServiceOfferingPDA(vendor2.publicKey, auctionOfferingDetails.offeringName, program.programId);
+      [auctionGroupAsset] = findOfferingGroupAssetPDA(auctionOffering, program.programId);
+      await createServiceOffering(program, vendor2, auctionOfferingDetails, auctionOffering, auctionGroupAsset);
+
+      auctionAsset = Keypair.generate();
+      [auction] = findAuctionPDA(auctionAsset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, auctionOfferingDetails, auctionOffering, auctionGroupAsset, buyer1, auctionAsset);
+    });
+
+    it("should fail to create an auction without a bid increment", async () => {
+      const endsAt = Math.floor(Date.now() / 1000) + 5;
+      await expectError(createAuction(program, buyer1, auctionAsset.publicKey, { reservePrice, minBidIncrement: 0, endsAt, extensionSecs: 0 }), "InvalidBidIncrement");
+    });
+
+    it("should lock the asset when creating an auction", async () => {
+      const endsAt = Math.floor(Date.now() / 1000) + 5;
+      const tx = await createAuction(program, buyer1, auctionAsset.publicKey, { reservePrice, minBidIncrement, endsAt, extensionSecs: 0 });
+      assert.ok(tx, "Transaction should be successful");
+
+      const assetHeader = await fetchAssetHeader(program.provider.connection, auctionAsset.publicKey);
+      assert.isTrue(assetHeader.locked, "Asset should be locked");
+      assert.equal(assetHeader.delegate.toBase58(), auction.toBase58(), "Auction should be the delegate");
+    });
+
+    it("should fail to bid below the reserve", async () => {
+      await expectError(placeBid(program, buyer2, auction, reservePrice - 1), "BidTooLow");
+    });
+
+    it("should refund the previous bidder when outbid", async () => {
+      const connection = program.provider.connection;
+      await placeBid(program, buyer2, auction, reservePrice);
+      const previousBidderBefore = await connection.getBalance(buyer2.publicKey, "processed");
+
+      const tx = await placeBid(program, vendor1, auction, reservePrice + minBidIncrement, buyer2.publicKey);
+      assert.ok(tx, "Transaction should be successful");
+
+      assert.equal(await connection.getBalance(buyer2.publicKey, "processed") - previousBidderBefore, reservePrice, "Previous bidder should be refunded");
+      const auctionAccount = await fetchAuction(program, auction);
+      assert.equal(auctionAccount.highestBidder.toBase58(), vendor1.publicKey.toBase58(), "Highest bidder doesn't match");
+    });
+
+    it("should fail to bid less than the minimum increment", async () => {
+      await expectError(placeBid(program, buyer2, auction, reservePrice + minBidIncrement + 1, vendor1.publicKey), "BidTooLow");
+    });
+
+    it("should fail to settle before the auction ends", async () => {
+      await expectError(settleAuction(program, buyer2, buyer1.publicKey, vendor1.publicKey, auctionAsset.publicKey, auctionGroupAsset, [vendor2.publicKey]), "AuctionNotEnded");
+    });
+
+    it("should transfer the asset to the winner and pay the seller on settlement", async () => {
+      const connection = program.provider.connection;
+      await new Promise((resolve) => setTimeout(resolve, 6_000));
+
+      const sellerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+      const tx = await settleAuction(program, buyer2, buyer1.publicKey, vendor1.publicKey, auctionAsset.publicKey, auctionGroupAsset, [vendor2.publicKey]);
+      assert.ok(tx, "Transaction should be successful");
+
+      const winningBid = reservePrice + minBidIncrement;
+      const royalty = (winningBid * auctionOfferingDetails.royaltyBasisPoints.toNumber()) / 10_000;
+      assert.isAtLeast(await connection.getBalance(buyer1.publicKey, "processed") - sellerBefore, winningBid - royalty, "Seller should receive the winning bid minus royalties");
+
+      const assetHeader = await fetchAssetHeader(connection, auctionAsset.publicKey);
+      assert.equal(assetHeader.owner.toBase58(), vendor1.publicKey.toBase58(), "Winner should own the asset");
+      assert.isFalse(assetHeader.locked, "Asset should be unlocked");
+      assert.isNull(await connection.getAccountInfo(auction, "processed"), "Auction should be closed");
+    });
+
+    it("should extend the auction when bidding near the end", async () => {
+      const asset = Keypair.generate();
+      const [snipeAuction] = findAuctionPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, auctionOfferingDetails, auctionOffering, auctionGroupAsset, buyer1, asset);
+
+      const endsAt = Math.floor(Date.now() / 1000) + 10;
+      await createAuction(program, buyer1, asset.publicKey, { reservePrice, minBidIncrement, endsAt, extensionSecs: 60 });
+      await placeBid(program, buyer2, snipeAuction, reservePrice);
+
+      const auctionAccount = await fetchAuction(program, snipeAuction);
+      assert.isAbove(auctionAccount.endsAt.toNumber(), endsAt, "Auction end should be extended");
+    });
+  });
+
+  describe("Declining Price Listings", () => {
+    const decliningOfferingDetails = { ...offeringDetails, offeringName: "Declining Offering", maxQuantity: 3 };
+    const startPrice = 2 * LAMPORTS_PER_SOL;
+    const endPrice = LAMPORTS_PER_SOL;
+    let decliningOffering, decliningGroupAsset;
+
+    before(async () => {
+      [decliningOffering] = findServiceOfferingPDA(vendor2.publicKey, decliningOfferingDetails.offeringName, program.programId);
+      [decliningGroupAsset] = findOfferingGroupAssetPDA(decliningOffering, program.programId);
+      await createServiceOffering(program, vendor2, decliningOfferingDetails, decliningOffering, decliningGroupAsset);
+    });
+
+    it("should fail to list a declining price without an expiry", async () => {
+      const asset = Keypair.generate();
+      const [decliningListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, decliningOfferingDetails, decliningOffering, decliningGroupAsset, buyer1, asset);
+
+      const pricing = { declining: { endPrice: new BN(endPrice), stepSecs: new BN(0) } };
+      await expectError(listAsset(program, { solPrice: startPrice, expiresAt: null, pricing }, buyer1, asset, decliningListing), "InvalidPricing");
+    });
+
+    it("should fail to update a declining listing", async () => {
+      const asset = Keypair.generate();
+      const [decliningListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, decliningOfferingDetails, decliningOffering, decliningGroupAsset, buyer1, asset);
+
+      const pricing = { declining: { endPrice: new BN(endPrice), stepSecs: new BN(0) } };
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 3600);
+      await listAsset(program, { solPrice: startPrice, expiresAt, pricing }, buyer1, asset, decliningListing);
+
+      await expectError(updateListing(program, decliningListing, buyer1, { solPrice: startPrice, expiresAt: expiresAt.addn(3600) }), "DecliningListingLocked");
+    });
+
+    it("should charge the end price once the listing has fully declined", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const [decliningListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      await buyService(program, vendor2, decliningOfferingDetails, decliningOffering, decliningGroupAsset, buyer1, asset);
+
+      const pricing = { declining: { endPrice: new BN(endPrice), stepSecs: new BN(0) } };
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 3);
+      await listAsset(program, { solPrice: startPrice, expiresAt, pricing }, buyer1, asset, decliningListing);
+
+      const listingAccount = await fetchListing(program, decliningListing);
+      assert.equal(listingAccount.pricing.declining.endPrice.toNumber(), endPrice, "End price doesn't match");
+
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
+      const buyerBefore = await connection.getBalance(buyer2.publicKey, "processed");
+      const tx = await buyListing(program, decliningListing, buyer2, asset, decliningGroupAsset, buyer1, [vendor2.publicKey]);
+      assert.ok(tx, "Transaction should be successful");
+
+      const paid = buyerBefore - await connection.getBalance(buyer2.publicKey, "processed");
+      assert.isBelow(paid, startPrice, "Buyer should pay less than the start price");
+      assert.isAtLeast(paid, endPrice, "Buyer should pay at least the end price");
+    });
+  });
+
+  describe("Batch Buy Listings", () => {
+    const batchOfferingDetails = { ...offeringDetails, offeringName: "Batch Offering", maxQuantity: 9 };
+    const listingPrice = LAMPORTS_PER_SOL / 10;
+    // Upper bound on the compute units each listing adds to a batch
+    const MAX_COMPUTE_UNITS_PER_LISTING = 60_000;
+    // Listings a client can put in one buy_listings transaction
+    const MAX_BATCH_SIZE = 5;
+    let batchOffering, batchGroupAsset;
+
+    const listBatch = async (count: number) => {
+      const items = [];
+      for (let i = 0; i < count; i++) {
+        const asset = Keypair.generate();
+        const [batchListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+        await buyService(program, vendor2, batchOfferingDetails, batchOffering, batchGroupAsset, buyer1, asset);
+        await listAsset(program, { solPrice: listingPrice, expiresAt: null }, buyer1, asset, batchListing);
+        items.push({ listing: batchListing, asset: asset.publicKey, seller: buyer1.publicKey, groupAsset: batchGroupAsset, creators: [vendor2.publicKey] });
+      }
+      return items;
+    };
+
+    before(async () => {
+      [batchOffering] = findServiceOfferingPDA(vendor2.publicKey, batchOfferingDetails.offeringName, program.programId);
+      [batchGroupAsset] = findOfferingGroupAssetPDA(batchOffering, program.programId);
+      await createServiceOffering(program, vendor2, batchOfferingDetails, batchOffering, batchGroupAsset);
+    });
+
+    it("should fail when the total exceeds the max total price", async () => {
+      const items = await listBatch(1);
+      await expectError(buyListings(program, buyer2, items, listingPrice - 1), "MaxTotalPriceExceeded");
+
+      await buyListings(program, buyer2, items, listingPrice);
+    });
+
+    it("should settle every listing in the batch", async () => {
+      const connection = program.provider.connection;
+      const items = await listBatch(3);
+      const sellerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+
+      const tx = await buyListings(program, buyer2, items, 3 * listingPrice);
+      assert.ok(tx, "Transaction should be successful");
+
+      for (const { listing: batchListing, asset } of items) {
+        assert.isNull(await connection.getAccountInfo(batchListing, "processed"), "Listing should be closed");
+        const assetHeader = await fetchAssetHeader(connection, asset);
+        assert.equal(assetHeader.owner.toBase58(), buyer2.publicKey.toBase58(), "Buyer should own the asset");
+        assert.isFalse(assetHeader.locked, "Asset should be unlocked");
+      }
+      assert.isAbove(await connection.getBalance(buyer1.publicKey, "processed"), sellerBefore, "Seller should be paid");
+    });
+
+    it("should fit a full batch under the transaction compute cap", async () => {
+      const items = await listBatch(MAX_BATCH_SIZE);
+      const singleUnits = await simulateBuyListings(program, buyer2, items.slice(0, 1), listingPrice);
+      const batchUnits = await simulateBuyListings(program, buyer2, items, MAX_BATCH_SIZE * listingPrice);
+      const unitsPerListing = (batchUnits - singleUnits) / (MAX_BATCH_SIZE - 1);
+
+      assert.isBelow(singleUnits, MAX_COMPUTE_UNITS_PER_LISTING, "Single listing is too expensive");
+      assert.isBelow(unitsPerListing, MAX_COMPUTE_UNITS_PER_LISTING, "Each listing should add a bounded compute cost");
+      assert.isBelow(batchUnits, MAX_TRANSACTION_COMPUTE_UNITS, "A full batch should fit in one transaction");
+    });
+  });
+
+  describe("Allowlist", () => {
+    const allowlistOfferingDetails = { ...offeringDetails, offeringName: "Allowlist Offering", maxQuantity: 6, allowlistRoot: null };
+    let allowlistOffering, allowlistGroupAsset, tree;
+
+    const buyAllowlisted = (buyer: Keypair, allocation: number, proof: number[][]) => {
+      const [purchaseCounter] = findPurchaseCounterPDA(allowlistOffering, buyer.publicKey, program.programId);
+      return buyService(program, vendor2, allowlistOfferingDetails, allowlistOffering, allowlistGroupAsset, buyer, Keypair.generate(), null, null, null, { purchaseCounter, allowlist: { allocation, proof } });
+    };
+
+    before(async () => {
+      // buyer1 may buy a single asset, buyer2 has no per-address limit
+      tree = buildAllowlistTree([
+        { buyer: buyer1.publicKey, allocation: 1 },
+        { buyer: buyer2.publicKey, allocation: 0 },
+        { buyer: Keypair.generate().publicKey, allocation: 3 },
+        { buyer: Keypair.generate().publicKey, allocation: 0 },
+        { buyer: Keypair.generate().publicKey, allocation: 2 },
+      ]);
+      allowlistOfferingDetails.allowlistRoot = tree.root;
+
+      [allowlistOffering] = findServiceOfferingPDA(vendor2.publicKey, allowlistOfferingDetails.offeringName, program.programId);
+      [allowlistGroupAsset] = findOfferingGroupAssetPDA(allowlistOffering, program.programId);
+      await createServiceOffering(program, vendor2, allowlistOfferingDetails, allowlistOffering, allowlistGroupAsset);
+    });
+
+    it("should fail to buy without a proof", async () => {
+      await expectError(buyService(program, vendor2, allowlistOfferingDetails, allowlistOffering, allowlistGroupAsset, vendor1, Keypair.generate()), "NotAllowlisted");
+    });
+
+    it("should fail to claim a different allocation", async () => {
+      await expectError(buyAllowlisted(buyer1, 0, tree.proof(0)), "NotAllowlisted");
+    });
+
+    it("should enforce the buyer's allocation", async () => {
+      const tx = await buyAllowlisted(buyer1, 1, tree.proof(0));
+      assert.ok(tx, "Transaction should be successful");
+
+      const [purchaseCounter] = findPurchaseCounterPDA(allowlistOffering, buyer1.publicKey, program.programId);
+      const counter = await program.account.purchaseCounter.fetch(purchaseCounter, "processed");
+      assert.equal(counter.purchased.toNumber(), 1, "Purchase should be counted");
+
+      await expectError(buyAllowlisted(buyer1, 1, tree.proof(0)), "AllowlistAllocationReached");
+    });
+
+    it("should let a buyer without an allocation buy repeatedly", async () => {
+      await buyAllowlisted(buyer2, 0, tree.proof(1));
+      const tx = await buyAllowlisted(buyer2, 0, tree.proof(1));
+      assert.ok(tx, "Transaction should be successful");
+    });
+
+    it("should fail to rotate the root from another wallet", async () => {
+      await expectError(updateServiceOfferingAllowlistRoot(program, vendor1, allowlistOfferingDetails.offeringName, allowlistOffering, null), "ConstraintSeeds");
+    });
+
+    it("should only accept proofs for the rotated root", async () => {
+      const rotatedTree = buildAllowlistTree([
+        { buyer: vendor1.publicKey, allocation: 0 },
+        { buyer: Keypair.generate().publicKey, allocation: 1 },
+      ]);
+      await updateServiceOfferingAllowlistRoot(program, vendor2, allowlistOfferingDetails.offeringName, allowlistOffering, rotatedTree.root);
+
+      const offeringAccount = await fetchServiceOffering(program, allowlistOffering);
+      assert.deepEqual(offeringAccount.allowlistRoot, rotatedTree.root, "Root should be rotated");
+
+      const tx = await buyAllowlisted(vendor1, 0, rotatedTree.proof(0));
+      assert.ok(tx, "Transaction should be successful");
+
+      await expectError(buyAllowlisted(buyer2, 0, tree.proof(1)), "NotAllowlisted");
+    });
+  });
+
+  describe("Per-Wallet Limits", () => {
+    const limitedOfferingDetails = { ...offeringDetails, offeringName: "Limited Offering", maxQuantity: 5, maxPerWallet: 2 };
+    let limitedOffering, limitedGroupAsset, purchaseCounter;
+
+    const buyLimited = (counter = purchaseCounter) =>
+      buyService(program, vendor2, limitedOfferingDetails, limitedOffering, limitedGroupAsset, buyer1, Keypair.generate(), null, null, null, { purchaseCounter: counter });
+
+    before(async () => {
+      [limitedOffering] = findServiceOfferingPDA(vendor2.publicKey, limitedOfferingDetails.offeringName, program.programId);
+      [limitedGroupAsset] = findOfferingGroupAssetPDA(limitedOffering, program.programId);
+      [purchaseCounter] = findPurchaseCounterPDA(limitedOffering, buyer1.publicKey, program.programId);
+      await createServiceOffering(program, vendor2, limitedOfferingDetails, limitedOffering, limitedGroupAsset);
+    });
+
+    it("should fail to buy without a purchase counter", async () => {
+      await expectError(buyLimited(null), "MissingPurchaseCounter");
+    });
+
+    it("should count purchases up to the limit", async () => {
+      await buyLimited();
+      await buyLimited();
+
+      const counter = await program.account.purchaseCounter.fetch(purchaseCounter, "processed");
+      assert.equal(counter.purchased.toNumber(), 2, "Purchases should be counted");
+      assert.equal(counter.buyer.toBase58(), buyer1.publicKey.toBase58(), "Buyer doesn't match");
+    });
+
+    it("should reject purchases over the limit", async () => {
+      await expectError(buyLimited(), "WalletLimitReached");
+
+      const offeringAccount = await fetchServiceOffering(program, limitedOffering);
+      assert.equal(offeringAccount.numSold.toNumber(), 2, "Only the purchases within the limit should be sold");
+    });
+
+    it("should track each wallet separately", async () => {
+      const [otherCounter] = findPurchaseCounterPDA(limitedOffering, buyer2.publicKey, program.programId);
+      const tx = await buyService(program, vendor2, limitedOfferingDetails, limitedOffering, limitedGroupAsset, buyer2, Keypair.generate(), null, null, null, { purchaseCounter: otherCounter });
+      assert.ok(tx, "Transaction should be successful");
+    });
+  });
+
+  describe("Scheduled Sales", () => {
+    const scheduledOfferingDetails = { ...offeringDetails, offeringName: "Scheduled Offering", maxQuantity: 2, startsAt: Math.floor(Date.now() / 1000) + 3600 };
+    let scheduledOffering, scheduledGroupAsset;
+
+    before(async () => {
+      [scheduledOffering] = findServiceOfferingPDA(vendor2.publicKey, scheduledOfferingDetails.offeringName, program.programId);
+      [scheduledGroupAsset] = findOfferingGroupAssetPDA(scheduledOffering, program.programId);
+      await createServiceOffering(program, vendor2, scheduledOfferingDetails, scheduledOffering, scheduledGroupAsset);
+    });
+
+    it("should fail to buy before the sale starts", async () => {
+      await expectError(buyService(program, vendor2, scheduledOfferingDetails, scheduledOffering, scheduledGroupAsset, buyer1, Keypair.generate()), "SaleNotStarted");
+    });
+
+    it("should fail to reschedule from another wallet", async () => {
+      await expectError(rescheduleServiceOffering(program, vendor1, scheduledOfferingDetails.offeringName, scheduledOffering, null), "ConstraintSeeds");
+    });
+
+    it("should sell once the rescheduled start is reached", async () => {
+      const startsAt = Math.floor(Date.now() / 1000) + 3;
+      await rescheduleServiceOffering(program, vendor2, scheduledOfferingDetails.offeringName, scheduledOffering, startsAt);
+
+      const offeringAccount = await fetchServiceOffering(program, scheduledOffering);
+      assert.equal(offeringAccount.startsAt.toNumber(), startsAt, "Start doesn't match");
+
+      await new Promise((resolve) => setTimeout(resolve, 5_000));
+      const tx = await buyService(program, vendor2, scheduledOfferingDetails, scheduledOffering, scheduledGroupAsset, buyer1, Keypair.generate());
+      assert.ok(tx, "Transaction should be successful");
+    });
+
+    it("should fail to reschedule after the sale has started", async () => {
+      await expectError(rescheduleServiceOffering(program, vendor2, scheduledOfferingDetails.offeringName, scheduledOffering, Math.floor(Date.now() / 1000) + 3600), "SaleAlreadyStarted");
+    });
+  });
+
+  describe("Tiers", () => {
+    const tieredOfferingDetails = {
+      ...offeringDetails,
+      offeringName: "Tiered Offering",
+      maxQuantity: 0,
+      tiers: [
+        { price: LAMPORTS_PER_SOL / 10, maxQuantity: 0 },
+        { price: LAMPORTS_PER_SOL / 2, maxQuantity: 1 },
+      ],
+    };
+    let tieredOffering, tieredGroupAsset;
+
+    const buyTier = (tier: number | null, asset = Keypair.generate()) =>
+      buyService(program, vendor2, tieredOfferingDetails, tieredOffering, tieredGroupAsset, buyer1, asset, null, null, null, null, tier);
+
+    before(async () => {
+      [tieredOffering] = findServiceOfferingPDA(vendor2.publicKey, tieredOfferingDetails.offeringName, program.programId);
+      [tieredGroupAsset] = findOfferingGroupAssetPDA(tieredOffering, program.programId);
+      await createServiceOffering(program, vendor2, tieredOfferingDetails, tieredOffering, tieredGroupAsset);
+    });
+
+    it("should fail to buy a tiered offering without a valid tier", async () => {
+      for (const tier of [null, 2]) {
+        await expectError(buyTier(tier), "InvalidTier");
+      }
+    });
+
+    it("should charge the tier price and record the tier on the asset", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+
+      const tx = await buyTier(1, asset);
+      assert.ok(tx, "Transaction should be successful");
+
+      const vendorProceeds = await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore;
+      assert.isAbove(vendorProceeds, LAMPORTS_PER_SOL / 10, "Vendor should be paid the Pro tier price");
+      assert.isAtMost(vendorProceeds, LAMPORTS_PER_SOL / 2, "Vendor should be paid the Pro tier price");
+      assert.equal(await fetchAssetAttribute(connection, asset.publicKey, "tier"), "1", "Tier attribute doesn't match");
+
+      const offeringAccount = await fetchServiceOffering(program, tieredOffering);
+      assert.equal(offeringAccount.tiers[1].numSold.toNumber(), 1, "Tier sold count doesn't match");
+      assert.equal(offeringAccount.numSold.toNumber(), 1, "Offering sold count doesn't match");
+    });
+
+    it("should cap each tier's supply separately", async () => {
+      await expectError(buyTier(1), "TierSoldOut");
+
+      const tx = await buyTier(0);
+      assert.ok(tx, "Transaction should be successful");
+    });
+
+    it("should reprice a tier", async () => {
+      const { offeringName } = tieredOfferingDetails;
+
+      await expectError(updateServiceOfferingPrice(program, vendor2, offeringName, tieredOffering, LAMPORTS_PER_SOL), "PriceNotUpdatable");
+      await expectError(updateServiceOfferingTierPrice(program, vendor2, offeringName, tieredOffering, 2, LAMPORTS_PER_SOL), "InvalidTier");
+      await updateServiceOfferingTierPrice(program, vendor2, offeringName, tieredOffering, 0, LAMPORTS_PER_SOL / 5);
+
+      const offeringAccount = await fetchServiceOffering(program, tieredOffering);
+      assert.equal(offeringAccount.tiers[0].price.toNumber(), LAMPORTS_PER_SOL / 5, "Tier price doesn't match");
+      assert.equal(offeringAccount.tiers[1].price.toNumber(), LAMPORTS_PER_SOL / 2, "Other tiers should keep their price");
+    });
+  });
+
+  describe("Coupons", () => {
+    const couponOfferingDetails = { ...offeringDetails, offeringName: "Coupon Offering", maxQuantity: 0 };
+    let couponOffering, couponGroupAsset;
+
+    const buyWithCoupon = (coupon, code: string) =>
+      buyService(program, vendor2, couponOfferingDetails, couponOffering, couponGroupAsset, buyer1, Keypair.generate(), null, null, null, null, null, { coupon, code });
+
+    const vendorProceeds = async (purchase: () => Promise<string>) => {
+      const connection = program.provider.connection;
+      const before = await connection.getBalance(vendor2.publicKey, "processed");
+      await purchase();
+      return await connection.getBalance(vendor2.publicKey, "processed") - before;
+    };
+
+    before(async () => {
+      [couponOffering] = findServiceOfferingPDA(vendor2.publicKey, couponOfferingDetails.offeringName, program.programId);
+      [couponGroupAsset] = findOfferingGroupAssetPDA(couponOffering, program.programId);
+      await createServiceOffering(program, vendor2, couponOfferingDetails, couponOffering, couponGroupAsset);
+    });
+
+    it("should apply a percentage discount and count the redemption", async () => {
+      const code = "HALF-OFF";
+      const codeHash = hashCouponCode(code);
+      const [coupon] = findCouponPDA(couponOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon, { codeHash, discount: { percentage: { bps: 5_000 } }, maxRedemptions: 2 });
+
+      const fullProceeds = await vendorProceeds(() =>
+        buyService(program, vendor2, couponOfferingDetails, couponOffering, couponGroupAsset, buyer1, Keypair.generate()));
+      const discountedProceeds = await vendorProceeds(() => buyWithCoupon(coupon, code));
+      assert.equal(discountedProceeds, fullProceeds / 2, "Vendor should receive half the price");
+
+      const couponAccount = await fetchCoupon(program, coupon);
+      assert.equal(couponAccount.redemptions.toNumber(), 1, "Redemption should be counted");
+    });
+
+    it("should apply a fixed discount", async () => {
+      const code = "TENTH-OFF";
+      const codeHash = hashCouponCode(code);
+      const [coupon] = findCouponPDA(couponOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon, { codeHash, discount: { fixed: { amount: new BN(couponOfferingDetails.solPrice) } } });
+
+      const proceeds = await vendorProceeds(() => buyWithCoupon(coupon, code));
+      assert.equal(proceeds, 0, "A discount of the full price makes the purchase free");
+    });
+
+    it("should fail with the wrong code", async () => {
+      const codeHash = hashCouponCode("SECRET");
+      const [coupon] = findCouponPDA(couponOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon, { codeHash, discount: { percentage: { bps: 1_000 } } });
+
+      await expectError(buyWithCoupon(coupon, "GUESS"), "InvalidCode");
+    });
+
+    it("should stop redemptions after the max and once expired", async () => {
+      const code = "ONCE";
+      const codeHash = hashCouponCode(code);
+      const [coupon] = findCouponPDA(couponOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon, { codeHash, discount: { percentage: { bps: 1_000 } }, maxRedemptions: 1 });
+      await buyWithCoupon(coupon, code);
+
+      const expiringCode = "SOON";
+      const expiringHash = hashCouponCode(expiringCode);
+      const [expiringCoupon] = findCouponPDA(couponOffering, expiringHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, expiringCoupon, { codeHash: expiringHash, discount: { percentage: { bps: 1_000 } }, expiresAt: Math.floor(Date.now() / 1000) + 2 });
+      await new Promise((resolve) => setTimeout(resolve, 3_000));
+
+      await expectError(buyWithCoupon(coupon, code), "CouponExhausted");
+      await expectError(buyWithCoupon(expiringCoupon, expiringCode), "CouponExpired");
+    });
+
+    it("should let the vendor close a coupon", async () => {
+      const codeHash = hashCouponCode("CLOSE-ME");
+      const [coupon] = findCouponPDA(couponOffering, codeHash, program.programId);
+      await createCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon, { codeHash, discount: { percentage: { bps: 1_000 } } });
+
+      const tx = await closeCoupon(program, vendor2, couponOfferingDetails.offeringName, couponOffering, coupon);
+      assert.ok(tx, "Transaction should be successful");
+      assert.isNull(await program.provider.connection.getAccountInfo(coupon, "processed"), "Coupon should be closed");
+    });
+  });
+
+  describe("Referrals", () => {
+    const referralOfferingDetails = { ...offeringDetails, offeringName: "Referral Offering", maxQuantity: 0, referralBps: 1_000 };
+    const referrer = Keypair.generate().publicKey;
+    let referralOffering, referralGroupAsset;
+
+    const buyReferred = (buyer: Keypair, referrer) =>
+      buyService(program, vendor2, referralOfferingDetails, referralOffering, referralGroupAsset, buyer, Keypair.generate(), null, null, null, null, null, null, referrer);
+    const register = (referrer) =>
+      registerReferrer(program, vendor2, referralOfferingDetails.offeringName, referralOffering, referrer);
+
+    before(async () => {
+      [referralOffering] = findServiceOfferingPDA(vendor2.publicKey, referralOfferingDetails.offeringName, program.programId);
+      [referralGroupAsset] = findOfferingGroupAssetPDA(referralOffering, program.programId);
+      await createServiceOffering(program, vendor2, referralOfferingDetails, referralOffering, referralGroupAsset);
+
+      // Referrers must be registered by the vendor and hold the rent-exempt minimum
+      await airdropToMultiple([referrer], program.provider.connection, LAMPORTS_PER_SOL);
+      await register(referrer);
+    });
+
+    it("should fail to create a referral offering paid in escrow", async () => {
+      const escrowDetails = { ...referralOfferingDetails, offeringName: "Escrowed Referral Offering", escrowTimeoutSecs: 60 };
+      const [escrowOffering] = findServiceOfferingPDA(vendor2.publicKey, escrowDetails.offeringName, program.programId);
+      const [escrowGroupAsset] = findOfferingGroupAssetPDA(escrowOffering, program.programId);
+
+      await expectError(createServiceOffering(program, vendor2, escrowDetails, escrowOffering, escrowGroupAsset), "UnsupportedReferral");
+    });
+
+    it("should fail to pay a referrer the vendor hasn't registered", async () => {
+      await expectError(buyReferred(buyer1, Keypair.generate().publicKey), "AccountNotInitialized");
+    });
+
+    it("should fail to pay a referrer below the rent-exempt minimum", async () => {
+      const unfundedReferrer = Keypair.generate().publicKey;
+      await register(unfundedReferrer);
+
+      await expectError(buyReferred(buyer1, unfundedReferrer), "ReferrerNotRentExempt");
+    });
+
+    it("should pay the referrer out of the vendor proceeds and track the stats", async () => {
+      const connection = program.provider.connection;
+      const referrerBefore = await connection.getBalance(referrer, "processed");
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+
+      await buyReferred(buyer1, referrer);
+      await buyReferred(buyer2, referrer);
+
+      const referrerEarned = await connection.getBalance(referrer, "processed") - referrerBefore;
+      const vendorEarned = await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore;
+      assert.isAbove(referrerEarned, 0, "Referrer should be paid");
+      assert.equal(referrerEarned * 9, vendorEarned, "Referrer should get 10% of the vendor proceeds");
+
+      const stats = await fetchReferralStats(program, referrer);
+      assert.equal(stats.referrals.toNumber(), 2, "Referrals should be counted");
+      assert.equal(stats.amountEarned.toNumber(), referrerEarned, "Earnings should be tracked");
+    });
+
+    it("should pay the referrer of a token-priced offering in the mint", async () => {
+      const connection = program.provider.connection;
+      const tokenReferrer = Keypair.generate().publicKey;
+      const paymentMint = await setupPaymentMint(connection, vendor2, [buyer1.publicKey, vendor2.publicKey, tokenReferrer], 100_000_000, TOKEN_PROGRAM_ID);
+      const ata = (owner) => getAssociatedTokenAddressSync(paymentMint, owner, false, TOKEN_PROGRAM_ID);
+      const tokenDetails = { ...referralOfferingDetails, offeringName: "Token Referral Offering", paymentMint, tokenPrice: 10_000_000 };
+      const [tokenOffering] = findServiceOfferingPDA(vendor2.publicKey, tokenDetails.offeringName, program.programId);
+      const [tokenGroupAsset] = findOfferingGroupAssetPDA(tokenOffering, program.programId);
+      await createServiceOffering(program, vendor2, tokenDetails, tokenOffering, tokenGroupAsset);
+      await registerReferrer(program, vendor2, tokenDetails.offeringName, tokenOffering, tokenReferrer, paymentMint);
+
+      // The referrer holds no SOL: token payouts don't need a rent-exempt wallet
+      const payment = { paymentMint, payerTokenAccount: ata(buyer1.publicKey), receiverTokenAccount: ata(vendor2.publicKey), tokenProgram: TOKEN_PROGRAM_ID };
+      await buyService(program, vendor2, tokenDetails, tokenOffering, tokenGroupAsset, buyer1, Keypair.generate(), null, payment, null, null, null, null, tokenReferrer, ata(tokenReferrer));
+
+      const referrerAccount = await getAccount(connection, ata(tokenReferrer), "processed", TOKEN_PROGRAM_ID);
+      const vendorAccount = await getAccount(connection, ata(vendor2.publicKey), "processed", TOKEN_PROGRAM_ID);
+      assert.equal(Number(referrerAccount.amount), 100_000_000 + 1_000_000, "Referrer should get 10% of the vendor proceeds in the mint");
+      assert.equal(Number(vendorAccount.amount), 100_000_000 + 9_000_000, "Vendor should get the rest");
+
+      const stats = await fetchReferralStats(program, tokenReferrer, paymentMint);
+      assert.equal(stats.referrals.toNumber(), 1, "Referral should be counted");
+      assert.equal(stats.amountEarned.toNumber(), 1_000_000, "Earnings should be tracked in the mint");
+      assert.equal(stats.paymentMint.toBase58(), paymentMint.toBase58(), "Stats should be kept per mint");
+    });
+
+    it("should fail to pay a token referral without the referrer token account", async () => {
+      const [tokenOffering] = findServiceOfferingPDA(vendor2.publicKey, "Token Referral Offering", program.programId);
+      const [tokenGroupAsset] = findOfferingGroupAssetPDA(tokenOffering, program.programId);
+      const { paymentMint } = await fetchServiceOffering(program, tokenOffering);
+      const ata = (owner) => getAssociatedTokenAddressSync(paymentMint, owner, false, TOKEN_PROGRAM_ID);
+      const tokenReferrer = Keypair.generate().publicKey;
+      await registerReferrer(program, vendor2, "Token Referral Offering", tokenOffering, tokenReferrer, paymentMint);
+
+      const payment = { paymentMint, payerTokenAccount: ata(buyer1.publicKey), receiverTokenAccount: ata(vendor2.publicKey), tokenProgram: TOKEN_PROGRAM_ID };
+      await expectError(buyService(program, vendor2, { ...referralOfferingDetails, offeringName: "Token Referral Offering" }, tokenOffering, tokenGroupAsset, buyer1, Keypair.generate(), null, payment, null, null, null, null, tokenReferrer), "MissingPaymentAccounts");
+    });
+
+    it("should fail when the buyer refers themselves", async () => {
+      await register(buyer1.publicKey);
+
+      await expectError(buyReferred(buyer1, buyer1.publicKey), "InvalidReferrer");
+    });
+
+    it("should stop paying a revoked referrer", async () => {
+      await revokeReferrer(program, vendor2, referralOfferingDetails.offeringName, referralOffering, referrer);
+
+      await expectError(buyReferred(buyer1, referrer), "AccountNotInitialized");
+    });
+  });
+
+  describe("Royalty Splits", () => {
+    const secondCreator = Keypair.generate().publicKey;
+    const splitOfferingDetails = {
+      ...offeringDetails,
+      offeringName: "Split Royalty Offering",
+      maxQuantity: 0,
+      royaltyBasisPoints: new BN(1_000),
+      creators: [
+        { address: vendor2.publicKey, share: 70 },
+        { address: secondCreator, share: 30 },
+      ],
+    };
+    let splitOffering, splitGroupAsset;
+
+    before(async () => {
+      [splitOffering] = findServiceOfferingPDA(vendor2.publicKey, splitOfferingDetails.offeringName, program.programId);
+      [splitGroupAsset] = findOfferingGroupAssetPDA(splitOffering, program.programId);
+      await createServiceOffering(program, vendor2, splitOfferingDetails, splitOffering, splitGroupAsset);
+    });
+
+    it("should fail to create an offering whose shares don't add up to 100", async () => {
+      const badDetails = {
+        ...splitOfferingDetails,
+        offeringName: "Bad Split Offering",
+        creators: [
+          { address: vendor2.publicKey, share: 60 },
+          { address: secondCreator, share: 30 },
+        ],
+      };
+      const [badOffering] = findServiceOfferingPDA(vendor2.publicKey, badDetails.offeringName, program.programId);
+      const [badGroupAsset] = findOfferingGroupAssetPDA(badOffering, program.programId);
+
+      await expectError(createServiceOffering(program, vendor2, badDetails, badOffering, badGroupAsset), "InvalidCreatorShares");
+    });
+
+    it("should fail to create an offering whose royalties leave no room for the protocol fee", async () => {
+      const badDetails = {
+        ...splitOfferingDetails,
+        offeringName: "Greedy Royalty Offering",
+        royaltyBasisPoints: new BN(9_500),
+      };
+      const [badOffering] = findServiceOfferingPDA(vendor2.publicKey, badDetails.offeringName, program.programId);
+      const [badGroupAsset] = findOfferingGroupAssetPDA(badOffering, program.programId);
+
+      await expectError(createServiceOffering(program, vendor2, badDetails, badOffering, badGroupAsset), "InvalidRoyaltyBps");
+    });
+
+    it("should split secondary royalties between the creators", async () => {
+      const connection = program.provider.connection;
+      const asset = Keypair.generate();
+      const [splitListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      const price = LAMPORTS_PER_SOL;
+
+      await buyService(program, vendor2, splitOfferingDetails, splitOffering, splitGroupAsset, buyer1, asset);
+      await listAsset(program, { solPrice: price, expiresAt: null }, buyer1, asset, splitListing);
+
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      await buyListing(program, splitListing, buyer2, asset, splitGroupAsset, buyer1, [vendor2.publicKey, secondCreator]);
+
+      const royalty = (price * splitOfferingDetails.royaltyBasisPoints.toNumber()) / 10_000;
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, (royalty * 70) / 100, "Vendor should receive 70% of the royalty");
+      assert.equal(await connection.getBalance(secondCreator, "processed"), (royalty * 30) / 100, "Second creator should receive 30% of the royalty");
+    });
+
+    it("should leave royalty shares below the rent-exempt minimum with the seller", async () => {
+      const connection = program.provider.connection;
+      const emptyCreators = [Keypair.generate().publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const dustOfferingDetails = {
+        ...splitOfferingDetails,
+        offeringName: "Dust Royalty Offering",
+        creators: [vendor2.publicKey, ...emptyCreators].map((address) => ({ address, share: 20 })),
+      };
+      const [dustOffering] = findServiceOfferingPDA(vendor2.publicKey, dustOfferingDetails.offeringName, program.programId);
+      const [dustGroupAsset] = findOfferingGroupAssetPDA(dustOffering, program.programId);
+      await createServiceOffering(program, vendor2, dustOfferingDetails, dustOffering, dustGroupAsset);
+
+      const asset = Keypair.generate();
+      const [dustListing] = findListingPDA(asset.publicKey, buyer1.publicKey, program.programId);
+      const price = LAMPORTS_PER_SOL / 100;
+      await buyService(program, vendor2, dustOfferingDetails, dustOffering, dustGroupAsset, buyer1, asset);
+      await listAsset(program, { solPrice: price, expiresAt: null }, buyer1, asset, dustListing);
+
+      const listingRent = await connection.getBalance(dustListing, "processed");
+      const sellerBefore = await connection.getBalance(buyer1.publicKey, "processed");
+      const vendorBefore = await connection.getBalance(vendor2.publicKey, "processed");
+      const tx = await buyListing(program, dustListing, buyer2, asset, dustGroupAsset, buyer1, [vendor2.publicKey, ...emptyCreators]);
+      assert.ok(tx, "Transaction should be successful");
 
+      // Each 20% share of the 10% royalty is far below the rent-exempt minimum of an empty wallet
+      const share = (price * dustOfferingDetails.royaltyBasisPoints.toNumber()) / 10_000 / 5;
+      assert.equal(await connection.getBalance(vendor2.publicKey, "processed") - vendorBefore, share, "Funded creator should be paid its share");
+      for (const creator of emptyCreators) {
+        assert.equal(await connection.getBalance(creator, "processed"), 0, "Empty creators should not be paid dust");
+      }
+      assert.equal(await connection.getBalance(buyer1.publicKey, "processed") - sellerBefore, price - share + listingRent, "Seller should keep the unpaid shares");
+    });
   });
 
   // Add more describe blocks for other functionalities
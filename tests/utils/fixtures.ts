@@ -3,6 +3,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { ServiceMarketplace } from "../../target/types/service_marketplace";
 import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { airdropToMultiple } from "../utils/utils";
+import { initializeMarketplaceConfig } from "../utils/transactions";
 
 export interface TestSetup {
   program: anchor.Program<ServiceMarketplace>;
@@ -10,6 +11,8 @@ export interface TestSetup {
   vendor2: Keypair;
   buyer1: Keypair;
   buyer2: Keypair;
+  admin: Keypair;
+  treasury: Keypair;
 }
 
 export async function setupTest(): Promise<TestSetup> {
@@ -22,7 +25,14 @@ export async function setupTest(): Promise<TestSetup> {
   const buyer1 = Keypair.generate();
   const buyer2 = Keypair.generate();
 
-  await airdropToMultiple([vendor1.publicKey, vendor2.publicKey, buyer1.publicKey, buyer2.publicKey], program.provider.connection, 100 * LAMPORTS_PER_SOL);
+  const admin = Keypair.generate();
+  const treasury = Keypair.generate();
 
-  return { program, vendor1, vendor2, buyer1, buyer2 };
+  await airdropToMultiple([vendor1.publicKey, vendor2.publicKey, buyer1.publicKey, buyer2.publicKey, admin.publicKey, treasury.publicKey], program.provider.connection, 100 * LAMPORTS_PER_SOL);
+
+  // Fees start at zero, the protocol fee tests set them explicitly.
+  // The admin doubles as the default dispute arbiter.
+  await initializeMarketplaceConfig(program, admin.publicKey, treasury.publicKey, admin.publicKey, 0, 0);
+
+  return { program, vendor1, vendor2, buyer1, buyer2, admin, treasury };
 }
\ No newline at end of file
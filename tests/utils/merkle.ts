@@ -0,0 +1,51 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+
+export interface AllowlistEntry {
+    buyer: PublicKey;
+    // 0 for no per-address limit
+    allocation: number;
+}
+
+const sha256 = (...parts: Buffer[]): Buffer => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Matches `allowlist_leaf` in the program: sha256(buyer || allocation as u64 LE)
+export function allowlistLeaf({ buyer, allocation }: AllowlistEntry): Buffer {
+    return sha256(buyer.toBuffer(), new BN(allocation).toArrayLike(Buffer, "le", 8));
+}
+
+// Pairs are hashed in sorted order, so proofs don't encode left/right positions
+function hashPair(a: Buffer, b: Buffer): Buffer {
+    return Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+}
+
+// Builds the Merkle tree of an allowlist, returning its root and a proof for each entry
+export function buildAllowlistTree(entries: AllowlistEntry[]) {
+    const layers: Buffer[][] = [entries.map(allowlistLeaf)];
+    while (layers[layers.length - 1].length > 1) {
+        const layer = layers[layers.length - 1];
+        const next: Buffer[] = [];
+        for (let i = 0; i < layer.length; i += 2) {
+            // An odd node out is promoted to the next layer as is
+            next.push(i + 1 < layer.length ? hashPair(layer[i], layer[i + 1]) : layer[i]);
+        }
+        layers.push(next);
+    }
+
+    const root = Array.from(layers[layers.length - 1][0]);
+
+    const proof = (index: number): number[][] => {
+        const siblings: number[][] = [];
+        for (const layer of layers.slice(0, -1)) {
+            const sibling = index % 2 === 0 ? index + 1 : index - 1;
+            if (sibling < layer.length) {
+                siblings.push(Array.from(layer[sibling]));
+            }
+            index = Math.floor(index / 2);
+        }
+        return siblings;
+    };
+
+    return { root, proof };
+}
@@ -1,7 +1,7 @@
 // tests/utils/pda-utils.ts
 
 import { PublicKey } from "@solana/web3.js";
-import { SEED_LISTING, SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP } from "./seeds";
+import { SEED_LISTING, SEED_SERVICE_OFFERING, SEED_SERVICE_OFFERING_GROUP, SEED_SUBSCRIPTION, SEED_MARKETPLACE_CONFIG, SEED_ORDER_ESCROW, SEED_OFFER, SEED_COLLECTION_BID, SEED_AUCTION, SEED_PURCHASE_COUNTER, SEED_COUPON, SEED_REFERRAL_STATS, SEED_REGISTERED_REFERRER } from "./seeds";
 
 export function findServiceOfferingPDA(
   vendor: PublicKey,
@@ -44,4 +44,155 @@ export function findListingPDA(
     ],
     programId
   );
+}
+
+export function findSubscriptionPDA(
+  asset: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_SUBSCRIPTION),
+      asset.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findMarketplaceConfigPDA(
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_MARKETPLACE_CONFIG),
+    ],
+    programId
+  );
+}
+
+export function findOrderEscrowPDA(
+  asset: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_ORDER_ESCROW),
+      asset.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findOfferPDA(
+  asset: PublicKey,
+  bidder: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_OFFER),
+      asset.toBuffer(),
+      bidder.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findCollectionBidPDA(
+  groupAsset: PublicKey,
+  bidder: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_COLLECTION_BID),
+      groupAsset.toBuffer(),
+      bidder.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findAuctionPDA(
+  asset: PublicKey,
+  seller: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_AUCTION),
+      asset.toBuffer(),
+      seller.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findPurchaseCounterPDA(
+  serviceOffering: PublicKey,
+  buyer: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_PURCHASE_COUNTER),
+      serviceOffering.toBuffer(),
+      buyer.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findCouponPDA(
+  serviceOffering: PublicKey,
+  codeHash: number[],
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_COUPON),
+      serviceOffering.toBuffer(),
+      Buffer.from(codeHash),
+    ],
+    programId
+  );
+}
+
+export function findReferralStatsPDA(
+  referrer: PublicKey,
+  paymentMint: PublicKey | null,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_REFERRAL_STATS),
+      referrer.toBuffer(),
+      (paymentMint ?? PublicKey.default).toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findRegisteredReferrerPDA(
+  serviceOffering: PublicKey,
+  referrer: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEED_REGISTERED_REFERRER),
+      serviceOffering.toBuffer(),
+      referrer.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function findProgramDataAddress(
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
 }
\ No newline at end of file
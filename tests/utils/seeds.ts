@@ -3,3 +3,27 @@ export const SEED_SERVICE_OFFERING: string = "service_offering";
 export const SEED_SERVICE_OFFERING_GROUP: string = "service_offering_group";
 
 export const SEED_LISTING: string = "listing";
+
+export const SEED_SUBSCRIPTION: string = "subscription";
+
+export const SEED_MARKETPLACE_CONFIG: string = "marketplace_config";
+
+export const SEED_ORDER_ESCROW: string = "order_escrow";
+
+export const SEED_OFFER: string = "offer";
+
+export const SEED_COLLECTION_BID: string = "collection_bid";
+
+export const SEED_AUCTION: string = "auction";
+
+export const SEED_PURCHASE_COUNTER: string = "purchase_counter";
+
+export const SEED_COUPON: string = "coupon";
+
+export const SEED_REFERRAL_STATS: string = "referral_stats";
+
+export const SEED_REGISTERED_REFERRER: string = "registered_referrer";
+
+export const EXPIRED_LISTING_BOUNTY: number = 100_000;
+
+export const MAX_PROTOCOL_FEE_BPS: number = 1_000;
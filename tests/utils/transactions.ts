@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { OSS_PROGRAM_ID } from "./keys";
 import { ServiceMarketplace } from "../../target/types/service_marketplace";
+import { findMarketplaceConfigPDA, findProgramDataAddress, findOrderEscrowPDA, findOfferPDA, findCollectionBidPDA, findAuctionPDA, findReferralStatsPDA, findRegisteredReferrerPDA } from "./pdas";
 
 export async function createServiceOffering(program, vendor, offeringDetails, serviceOffering, offeringGroupAsset) {
     return program.methods
@@ -16,6 +17,19 @@ export async function createServiceOffering(program, vendor, offeringDetails, se
             offeringDetails.royaltyBasisPoints,
             offeringDetails.termsOfServiceUri,
             offeringDetails.isTransferrable,
+            offeringDetails.serviceType ?? { oneTime: {} },
+            offeringDetails.paymentMint ?? null,
+            new anchor.BN(offeringDetails.tokenPrice ?? 0),
+            offeringDetails.escrowTimeoutSecs != null ? new anchor.BN(offeringDetails.escrowTimeoutSecs) : null,
+            offeringDetails.arbiter ?? null,
+            offeringDetails.redeemMode ?? { attribute: {} },
+            offeringDetails.usesPerAsset != null ? new anchor.BN(offeringDetails.usesPerAsset) : null,
+            offeringDetails.allowlistRoot ?? null,
+            new anchor.BN(offeringDetails.maxPerWallet ?? 0),
+            offeringDetails.startsAt != null ? new anchor.BN(offeringDetails.startsAt) : null,
+            (offeringDetails.tiers ?? []).map((tier) => ({ price: new anchor.BN(tier.price), maxQuantity: new anchor.BN(tier.maxQuantity) })),
+            offeringDetails.referralBps ?? 0,
+            (offeringDetails.creators ?? []).map((creator) => ({ address: creator.address, share: creator.share })),
         )
         .accountsPartial({
             vendor: vendor.publicKey,
@@ -32,13 +46,49 @@ export async function fetchServiceOffering(program, serviceOffering) {
     return program.account.serviceOffering.fetch(serviceOffering);
 }
 
-export async function buyService(program, vendor, offeringDetails, serviceOffering, offeringGroupAsset, buyer, newAsset) {
+export interface TokenPaymentAccounts {
+    paymentMint: anchor.web3.PublicKey;
+    payerTokenAccount: anchor.web3.PublicKey;
+    receiverTokenAccount: anchor.web3.PublicKey;
+    tokenProgram: anchor.web3.PublicKey;
+    treasuryTokenAccount?: anchor.web3.PublicKey;
+}
+
+// Marketplace config and treasury accounts required by every sale
+async function marketplaceFeeAccounts(program) {
+    const [marketplaceConfig] = findMarketplaceConfigPDA(program.programId);
+    const { treasury } = await program.account.marketplaceConfig.fetch(marketplaceConfig, "processed");
+
+    return { marketplaceConfig, treasury };
+}
+
+// Purchase counter (for allowlist allocations and per-wallet limits) and allowlist proof
+export interface PurchaseLimitAccounts {
+    purchaseCounter?: anchor.web3.PublicKey;
+    allowlist?: { allocation: number; proof: number[][] };
+}
+
+export async function buyService(program, vendor, offeringDetails, serviceOffering, offeringGroupAsset, buyer, newAsset, subscription = null, payment: TokenPaymentAccounts | null = null, orderEscrow = null, limits: PurchaseLimitAccounts | null = null, tier: number | null = null, coupon: { coupon: anchor.web3.PublicKey; code: string } | null = null, referrer: anchor.web3.PublicKey | null = null, referrerTokenAccount: anchor.web3.PublicKey | null = null) {
     const accounts = {
         buyer: buyer.publicKey,
         vendor: vendor.publicKey,
         serviceOffering,
         offeringGroupAsset,
         newAsset: newAsset.publicKey,
+        subscription,
+        orderEscrow,
+        purchaseCounter: limits?.purchaseCounter ?? null,
+        coupon: coupon?.coupon ?? null,
+        referrer,
+        registeredReferrer: referrer ? findRegisteredReferrerPDA(serviceOffering, referrer, program.programId)[0] : null,
+        referralStats: referrer ? findReferralStatsPDA(referrer, payment?.paymentMint ?? null, program.programId)[0] : null,
+        paymentMint: payment?.paymentMint ?? null,
+        buyerTokenAccount: payment?.payerTokenAccount ?? null,
+        vendorTokenAccount: payment?.receiverTokenAccount ?? null,
+        referrerTokenAccount,
+        tokenProgram: payment?.tokenProgram ?? null,
+        ...(await marketplaceFeeAccounts(program)),
+        treasuryTokenAccount: payment?.treasuryTokenAccount ?? null,
         ossProgram: OSS_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
     }
@@ -46,6 +96,9 @@ export async function buyService(program, vendor, offeringDetails, serviceOfferi
     return program.methods
         .buyService(
             offeringDetails.offeringName,
+            limits?.allowlist ? { allocation: new anchor.BN(limits.allowlist.allocation), proof: limits.allowlist.proof } : null,
+            tier,
+            coupon?.code ?? null,
         )
         .accountsPartial(accounts)
         .signers([buyer, newAsset])
@@ -65,6 +118,8 @@ export async function listAsset(program, listingDetails, seller, asset, listing)
         .listAsset(
             new anchor.BN(listingDetails.solPrice),
             listingDetails.expiresAt,
+            listingDetails.paymentMint ?? null,
+            listingDetails.pricing ?? { fixed: {} },
         )
         .accountsPartial(accounts)
         .signers([seller])
@@ -83,26 +138,640 @@ export async function buyListing(
     asset, 
     groupAsset, 
     seller, 
-    royaltyReceiver
+    creators: anchor.web3.PublicKey[],
+    payment: TokenPaymentAccounts | null = null,
+    maxPrice: number | null = null
 ) {
+    // Defaults to the listed price, which bounds the price of declining listings
+    const maxPriceArg = maxPrice != null
+        ? new anchor.BN(maxPrice)
+        : (await fetchListing(program, listing)).price;
+
     const accounts = {
         buyer: buyer.publicKey,
         seller: seller.publicKey,
         asset: asset.publicKey,
         groupAsset,
-        royaltyReceiver: royaltyReceiver.publicKey,
         listing,
+        paymentMint: payment?.paymentMint ?? null,
+        buyerTokenAccount: payment?.payerTokenAccount ?? null,
+        sellerTokenAccount: payment?.receiverTokenAccount ?? null,
+        tokenProgram: payment?.tokenProgram ?? null,
+        ...(await marketplaceFeeAccounts(program)),
+        treasuryTokenAccount: payment?.treasuryTokenAccount ?? null,
         ossProgram: OSS_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
     }
 
+    // Creators (or their token accounts for token listings) must be passed in
+    // the same order as the group's Creators extension
+    const remainingAccounts = creators.map((creator) => ({
+        pubkey: creator,
+        isWritable: true,
+        isSigner: false,
+    }));
+
+    return program.methods
+        .buyListing(maxPriceArg)
+        .accountsPartial(accounts)
+        .remainingAccounts(remainingAccounts)
+        .signers([buyer])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+function buyListingsMethod(program, buyer, items, maxTotalPrice) {
+    // Each listing is passed as listing, asset, seller, group asset, followed by
+    // the group's creators in the same order as its Creators extension
+    const remainingAccounts = items.flatMap(({ listing, asset, seller, groupAsset, creators }) =>
+        [listing, asset, seller, groupAsset, ...creators].map((pubkey) => ({
+            pubkey,
+            isWritable: true,
+            isSigner: false,
+        }))
+    );
+
+    return program.methods
+        .buyListings(new anchor.BN(maxTotalPrice))
+        .accountsPartial({
+            buyer: buyer.publicKey,
+            ossProgram: OSS_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts);
+}
+
+export async function buyListings(program, buyer, items, maxTotalPrice) {
+    return buyListingsMethod(program, buyer, items, maxTotalPrice)
+        .accountsPartial(await marketplaceFeeAccounts(program))
+        .signers([buyer])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+// Compute unit cap of a single transaction
+export const MAX_TRANSACTION_COMPUTE_UNITS = 1_400_000;
+
+// Simulates a batch purchase under the transaction compute cap and returns
+// the compute units it consumes
+export async function simulateBuyListings(program, buyer, items, maxTotalPrice): Promise<number> {
+    const tx = await buyListingsMethod(program, buyer, items, maxTotalPrice)
+        .accountsPartial(await marketplaceFeeAccounts(program))
+        .preInstructions([anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: MAX_TRANSACTION_COMPUTE_UNITS })])
+        .transaction();
+    const { value } = await program.provider.connection.simulateTransaction(tx, [buyer]);
+    if (value.err) {
+        throw new Error(`Simulation failed: ${JSON.stringify(value.err)}`);
+    }
+
+    return value.unitsConsumed;
+}
+
+export async function cancelListing(program, listing, seller, asset) {
+    const accounts = {
+        seller: seller.publicKey,
+        asset: asset.publicKey,
+        listing,
+        ossProgram: OSS_PROGRAM_ID,
+    }
+
+    return program.methods
+        .cancelListing()
+        .accountsPartial(accounts)
+        .signers([seller])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function closeExpiredListing(program, listing, cranker, seller, asset) {
+    const accounts = {
+        cranker: cranker.publicKey,
+        seller: seller.publicKey,
+        asset: asset.publicKey,
+        listing,
+        ossProgram: OSS_PROGRAM_ID,
+    }
+
+    return program.methods
+        .closeExpiredListing()
+        .accountsPartial(accounts)
+        .signers([cranker])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateListing(program, listing, seller, listingDetails) {
+    const accounts = {
+        seller: seller.publicKey,
+        listing,
+    }
+
+    return program.methods
+        .updateListing(
+            listingDetails.solPrice != null ? new anchor.BN(listingDetails.solPrice) : null,
+            listingDetails.expiresAt ?? null,
+        )
+        .accountsPartial(accounts)
+        .signers([seller])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function activateServiceOffering(program, vendor, offeringName, serviceOffering) {
+    return program.methods
+        .activateServiceOffering(offeringName)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function deactivateServiceOffering(program, vendor, offeringName, serviceOffering) {
+    return program.methods
+        .deactivateServiceOffering(offeringName)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateServiceOfferingPrice(program, vendor, offeringName, serviceOffering, solPrice) {
+    return program.methods
+        .updateServiceOfferingPrice(offeringName, new anchor.BN(solPrice))
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateServiceOfferingTokenPrice(program, vendor, offeringName, serviceOffering, tokenPrice) {
+    return program.methods
+        .updateServiceOfferingTokenPrice(offeringName, new anchor.BN(tokenPrice))
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateServiceOfferingTierPrice(program, vendor, offeringName, serviceOffering, tier, price) {
+    return program.methods
+        .updateServiceOfferingTierPrice(offeringName, tier, new anchor.BN(price))
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateServiceOfferingMaxQuantity(program, vendor, offeringName, serviceOffering, maxQuantity) {
+    return program.methods
+        .updateServiceOfferingMaxQuantity(offeringName, new anchor.BN(maxQuantity))
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateServiceOfferingAllowlistRoot(program, vendor, offeringName, serviceOffering, allowlistRoot: number[] | null) {
+    return program.methods
+        .updateServiceOfferingAllowlistRoot(offeringName, allowlistRoot)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function rescheduleServiceOffering(program, vendor, offeringName, serviceOffering, startsAt: number | null) {
+    return program.methods
+        .rescheduleServiceOffering(offeringName, startsAt != null ? new anchor.BN(startsAt) : null)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function createCoupon(program, vendor, offeringName, serviceOffering, coupon, couponDetails) {
+    return program.methods
+        .createCoupon(
+            offeringName,
+            couponDetails.codeHash,
+            couponDetails.discount,
+            new anchor.BN(couponDetails.maxRedemptions ?? 0),
+            couponDetails.expiresAt != null ? new anchor.BN(couponDetails.expiresAt) : null,
+        )
+        .accountsPartial({
+            vendor: vendor.publicKey,
+            serviceOffering,
+            coupon,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function closeCoupon(program, vendor, offeringName, serviceOffering, coupon) {
+    return program.methods
+        .closeCoupon(offeringName)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering, coupon })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function registerReferrer(program, vendor, offeringName, serviceOffering, referrer: anchor.web3.PublicKey, paymentMint: anchor.web3.PublicKey | null = null) {
+    const [registeredReferrer] = findRegisteredReferrerPDA(serviceOffering, referrer, program.programId);
+    const [referralStats] = findReferralStatsPDA(referrer, paymentMint, program.programId);
+    return program.methods
+        .registerReferrer(offeringName, referrer)
+        .accountsPartial({
+            vendor: vendor.publicKey,
+            serviceOffering,
+            registeredReferrer,
+            referralStats,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function revokeReferrer(program, vendor, offeringName, serviceOffering, referrer: anchor.web3.PublicKey) {
+    const [registeredReferrer] = findRegisteredReferrerPDA(serviceOffering, referrer, program.programId);
+    return program.methods
+        .revokeReferrer(offeringName)
+        .accountsPartial({ vendor: vendor.publicKey, serviceOffering, registeredReferrer })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function fetchReferralStats(program: anchor.Program<ServiceMarketplace>, referrer: anchor.web3.PublicKey, paymentMint: anchor.web3.PublicKey | null = null) {
+    const [referralStats] = findReferralStatsPDA(referrer, paymentMint, program.programId);
+    return program.account.referralStats.fetch(referralStats, "processed");
+}
+
+export async function fetchCoupon(program: anchor.Program<ServiceMarketplace>, coupon) {
+    return program.account.coupon.fetch(coupon, "processed");
+}
+
+export async function renewSubscription(program, payer, vendor, offeringName, serviceOffering, subscription, payment: TokenPaymentAccounts | null = null) {
+    const accounts = {
+        payer: payer.publicKey,
+        vendor: vendor.publicKey,
+        serviceOffering,
+        subscription,
+        paymentMint: payment?.paymentMint ?? null,
+        payerTokenAccount: payment?.payerTokenAccount ?? null,
+        vendorTokenAccount: payment?.receiverTokenAccount ?? null,
+        tokenProgram: payment?.tokenProgram ?? null,
+        ...(await marketplaceFeeAccounts(program)),
+        treasuryTokenAccount: payment?.treasuryTokenAccount ?? null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+    }
+
     return program.methods
-        .buyListing()
+        .renewSubscription(offeringName)
         .accountsPartial(accounts)
+        .signers([payer])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function checkSubscription(program, holder, asset, subscription): Promise<boolean> {
+    return program.methods
+        .checkSubscription()
+        .accountsPartial({ holder, asset, subscription })
+        .view({ commitment: "processed" });
+}
+
+export async function fetchSubscription(program: anchor.Program<ServiceMarketplace>, subscription) {
+    return program.account.subscription.fetch(subscription);
+}
+
+export async function initializeMarketplaceConfig(program, admin, treasury, arbiter, primaryFeeBps, secondaryFeeBps) {
+    const [marketplaceConfig] = findMarketplaceConfigPDA(program.programId);
+    const [programData] = findProgramDataAddress(program.programId);
+
+    // The provider wallet deploys the program, so it is the upgrade authority
+    return program.methods
+        .initializeMarketplaceConfig(admin, treasury, arbiter, primaryFeeBps, secondaryFeeBps)
+        .accountsPartial({
+            authority: program.provider.wallet.publicKey,
+            marketplaceConfig,
+            program: program.programId,
+            programData,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function updateMarketplaceConfig(program, admin, newAdmin, treasury, arbiter, primaryFeeBps, secondaryFeeBps) {
+    const [marketplaceConfig] = findMarketplaceConfigPDA(program.programId);
+
+    return program.methods
+        .updateMarketplaceConfig(newAdmin, treasury, arbiter, primaryFeeBps, secondaryFeeBps)
+        .accountsPartial({ admin: admin.publicKey, marketplaceConfig })
+        .signers([admin])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function markFulfilled(program, vendor, asset) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .markFulfilled()
+        .accountsPartial({ vendor: vendor.publicKey, orderEscrow })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function confirmFulfillment(program, buyer, vendor, asset) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .confirmFulfillment()
+        .accountsPartial({
+            buyer: buyer.publicKey,
+            vendor: vendor.publicKey,
+            orderEscrow,
+            ...(await marketplaceFeeAccounts(program)),
+        })
         .signers([buyer])
         .rpc({ skipPreflight: true, commitment: "processed" });
 }
 
+export async function releaseEscrow(program, cranker, buyer, vendor, asset) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .releaseEscrow()
+        .accountsPartial({
+            cranker: cranker.publicKey,
+            buyer,
+            vendor,
+            orderEscrow,
+            ...(await marketplaceFeeAccounts(program)),
+        })
+        .signers([cranker])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function requestRefund(program, buyer, serviceOffering, offeringGroupAsset, asset, purchaseCounter = null, coupon = null) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .requestRefund()
+        .accountsPartial({
+            buyer: buyer.publicKey,
+            orderEscrow,
+            serviceOffering,
+            asset,
+            offeringGroupAsset,
+            purchaseCounter,
+            coupon,
+            ossProgram: OSS_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function openDispute(program, party, asset) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .openDispute()
+        .accountsPartial({ party: party.publicKey, orderEscrow })
+        .signers([party])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function submitEvidenceUri(program, party, asset, uri) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .submitEvidenceUri(uri)
+        .accountsPartial({ party: party.publicKey, orderEscrow })
+        .signers([party])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function resolveDispute(program, arbiter, buyer, vendor, asset, buyerBps) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .resolveDispute(buyerBps)
+        .accountsPartial({
+            arbiter: arbiter.publicKey,
+            buyer,
+            vendor,
+            orderEscrow,
+            ...(await marketplaceFeeAccounts(program)),
+        })
+        .signers([arbiter])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function resolveExpiredDispute(program, cranker, buyer, vendor, asset) {
+    const [orderEscrow] = findOrderEscrowPDA(asset, program.programId);
+
+    return program.methods
+        .resolveExpiredDispute()
+        .accountsPartial({
+            cranker: cranker.publicKey,
+            buyer,
+            vendor,
+            orderEscrow,
+            ...(await marketplaceFeeAccounts(program)),
+        })
+        .signers([cranker])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function redeemService(program, holder, vendor, offeringName, serviceOffering, offeringGroupAsset, asset) {
+    return program.methods
+        .redeemService(offeringName)
+        .accountsPartial({
+            holder: holder.publicKey,
+            vendor: vendor.publicKey,
+            serviceOffering,
+            asset,
+            offeringGroupAsset,
+            ossProgram: OSS_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([holder, vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function consumeUse(program, vendor, offeringName, serviceOffering, asset) {
+    return program.methods
+        .consumeUse(offeringName)
+        .accountsPartial({
+            vendor: vendor.publicKey,
+            serviceOffering,
+            asset,
+            ossProgram: OSS_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([vendor])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function makeOffer(program, bidder, asset, price, expiresAt = null) {
+    const [offer] = findOfferPDA(asset, bidder.publicKey, program.programId);
+
+    return program.methods
+        .makeOffer(new anchor.BN(price), expiresAt)
+        .accountsPartial({
+            bidder: bidder.publicKey,
+            asset,
+            offer,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([bidder])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function cancelOffer(program, bidder, asset) {
+    const [offer] = findOfferPDA(asset, bidder.publicKey, program.programId);
+
+    return program.methods
+        .cancelOffer()
+        .accountsPartial({ bidder: bidder.publicKey, offer })
+        .signers([bidder])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function acceptOffer(program, seller, bidder, asset, groupAsset, creators: anchor.web3.PublicKey[]) {
+    const [offer] = findOfferPDA(asset, bidder, program.programId);
+    const remainingAccounts = creators.map((creator) => ({
+        pubkey: creator,
+        isWritable: true,
+        isSigner: false,
+    }));
+
+    return program.methods
+        .acceptOffer()
+        .accountsPartial({
+            seller: seller.publicKey,
+            bidder,
+            asset,
+            groupAsset,
+            offer,
+            ...(await marketplaceFeeAccounts(program)),
+            ossProgram: OSS_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([seller])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function fetchOffer(program: anchor.Program<ServiceMarketplace>, offer) {
+    return program.account.offer.fetch(offer);
+}
+
+export async function placeCollectionBid(program, bidder, groupAsset, price, quantity, expiresAt = null, minRemainingUses = 0, tier: number | null = null) {
+    const [collectionBid] = findCollectionBidPDA(groupAsset, bidder.publicKey, program.programId);
+
+    return program.methods
+        .placeCollectionBid(new anchor.BN(price), new anchor.BN(quantity), expiresAt, new anchor.BN(minRemainingUses), tier)
+        .accountsPartial({
+            bidder: bidder.publicKey,
+            groupAsset,
+            collectionBid,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([bidder])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function cancelCollectionBid(program, bidder, groupAsset) {
+    const [collectionBid] = findCollectionBidPDA(groupAsset, bidder.publicKey, program.programId);
+
+    return program.methods
+        .cancelCollectionBid()
+        .accountsPartial({ bidder: bidder.publicKey, collectionBid })
+        .signers([bidder])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function fillCollectionBid(program, seller, bidder, asset, groupAsset, creators: anchor.web3.PublicKey[]) {
+    const [collectionBid] = findCollectionBidPDA(groupAsset, bidder, program.programId);
+    const remainingAccounts = creators.map((creator) => ({
+        pubkey: creator,
+        isWritable: true,
+        isSigner: false,
+    }));
+
+    return program.methods
+        .fillCollectionBid()
+        .accountsPartial({
+            seller: seller.publicKey,
+            bidder,
+            asset,
+            groupAsset,
+            collectionBid,
+            ...(await marketplaceFeeAccounts(program)),
+            ossProgram: OSS_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([seller])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function fetchCollectionBid(program: anchor.Program<ServiceMarketplace>, collectionBid) {
+    return program.account.collectionBid.fetch(collectionBid);
+}
+
+export async function createAuction(program, seller, asset, auctionDetails) {
+    const [auction] = findAuctionPDA(asset, seller.publicKey, program.programId);
+
+    return program.methods
+        .createAuction(
+            new anchor.BN(auctionDetails.reservePrice),
+            new anchor.BN(auctionDetails.minBidIncrement),
+            new anchor.BN(auctionDetails.endsAt),
+            new anchor.BN(auctionDetails.extensionSecs),
+        )
+        .accountsPartial({
+            seller: seller.publicKey,
+            asset,
+            auction,
+            ossProgram: OSS_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([seller])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function placeBid(program, bidder, auction, amount, previousBidder = null) {
+    return program.methods
+        .placeBid(new anchor.BN(amount))
+        .accountsPartial({
+            bidder: bidder.publicKey,
+            auction,
+            previousBidder,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([bidder])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function settleAuction(program, payer, seller, winner, asset, groupAsset, creators: anchor.web3.PublicKey[]) {
+    const [auction] = findAuctionPDA(asset, seller, program.programId);
+    const remainingAccounts = creators.map((creator) => ({
+        pubkey: creator,
+        isWritable: true,
+        isSigner: false,
+    }));
+
+    return program.methods
+        .settleAuction()
+        .accountsPartial({
+            payer: payer.publicKey,
+            seller,
+            winner,
+            asset,
+            groupAsset,
+            auction,
+            ...(await marketplaceFeeAccounts(program)),
+            ossProgram: OSS_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([payer])
+        .rpc({ skipPreflight: true, commitment: "processed" });
+}
+
+export async function fetchAuction(program: anchor.Program<ServiceMarketplace>, auction) {
+    return program.account.auction.fetch(auction);
+}
+
+export async function fetchOrderEscrow(program: anchor.Program<ServiceMarketplace>, orderEscrow) {
+    return program.account.orderEscrow.fetch(orderEscrow);
+}
+
 
 
 
@@ -1,4 +1,8 @@
-import { Connection, PublicKey } from "@solana/web3.js";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { createHash } from "crypto";
+import { AnchorError } from "@coral-xyz/anchor";
+import { assert } from "chai";
 
 async function airdropToMultiple(
     pubkeys: PublicKey[],
@@ -19,4 +23,105 @@ async function airdropToMultiple(
     }
 }
 
-export { airdropToMultiple };
\ No newline at end of file
+// Reads the fixed-size header of a Nifty Asset account
+async function fetchAssetHeader(
+    connection: Connection,
+    asset: PublicKey
+): Promise<{ locked: boolean; owner: PublicKey; delegate: PublicKey | null; delegateRoles: number }> {
+    const accountInfo = await connection.getAccountInfo(asset, "processed");
+    if (!accountInfo) {
+        throw new Error(`Asset ${asset.toBase58()} not found`);
+    }
+    const data = accountInfo.data;
+    const delegate = new PublicKey(data.subarray(100, 132));
+
+    return {
+        locked: data[1] === 1,
+        owner: new PublicKey(data.subarray(4, 36)),
+        delegate: delegate.equals(PublicKey.default) ? null : delegate,
+        delegateRoles: data[132],
+    };
+}
+
+// Reads a trait from the Attributes extension of a Nifty Asset. Traits are stored
+// as length-prefixed name and value strings.
+async function fetchAssetAttribute(
+    connection: Connection,
+    asset: PublicKey,
+    name: string
+): Promise<string | null> {
+    const accountInfo = await connection.getAccountInfo(asset, "processed");
+    if (!accountInfo) {
+        throw new Error(`Asset ${asset.toBase58()} not found`);
+    }
+    const data = accountInfo.data;
+    const key = Buffer.concat([Buffer.from([name.length]), Buffer.from(name)]);
+    const offset = data.indexOf(key);
+    if (offset < 0) {
+        return null;
+    }
+
+    const valueOffset = offset + key.length;
+    return data.subarray(valueOffset + 1, valueOffset + 1 + data[valueOffset]).toString();
+}
+
+// Coupons are stored under the sha256 of their secret code
+function hashCouponCode(code: string): number[] {
+    return Array.from(createHash("sha256").update(code).digest());
+}
+
+// Creates a 6-decimal mint under `tokenProgram` and funds each holder's associated token account
+async function setupPaymentMint(
+    connection: Connection,
+    authority: Keypair,
+    holders: PublicKey[],
+    amount: number,
+    tokenProgram: PublicKey
+): Promise<PublicKey> {
+    const mint = await createMint(connection, authority, authority.publicKey, null, 6, Keypair.generate(), { commitment: "processed" }, tokenProgram);
+
+    for (const holder of holders) {
+        const tokenAccount = await getOrCreateAssociatedTokenAccount(connection, authority, mint, holder, false, "processed", { commitment: "processed" }, tokenProgram);
+        if (amount > 0) {
+            await mintTo(connection, authority, mint, tokenAccount.address, authority, amount, [], { commitment: "processed" }, tokenProgram);
+        }
+    }
+
+    return mint;
+}
+
+// Reads the Anchor error code (e.g. "SaleNotStarted") a transaction failed with.
+// Transactions are sent with skipPreflight, so the error may come without logs,
+// in which case they are read from the failed transaction once it is confirmed.
+async function anchorErrorCode(connection: Connection, error): Promise<string | undefined> {
+    if (error?.error?.errorCode?.code) {
+        return error.error.errorCode.code;
+    }
+
+    let logs: string[] | undefined = error?.logs;
+    const signature = /Raw transaction (\w+) failed/.exec(error?.message ?? "")?.[1];
+    for (let attempt = 0; !logs && signature && attempt < 20; attempt++) {
+        const transaction = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+        logs = transaction?.meta?.logMessages ?? undefined;
+        if (!logs) {
+            await new Promise((resolve) => setTimeout(resolve, 250));
+        }
+    }
+
+    return logs ? AnchorError.parse(logs)?.error.errorCode.code : undefined;
+}
+
+// Asserts that `transaction` fails with the Anchor error `code`
+async function expectAnchorError(connection: Connection, transaction: Promise<unknown>, code: string): Promise<void> {
+    let error;
+    try {
+        await transaction;
+    } catch (caught) {
+        error = caught ?? new Error("Transaction failed without an error");
+    }
+
+    assert.isDefined(error, `Transaction should have failed with ${code}`);
+    assert.equal(await anchorErrorCode(connection, error), code, `Unexpected error: ${error}`);
+}
+
+export { airdropToMultiple, expectAnchorError, fetchAssetHeader, fetchAssetAttribute, hashCouponCode, setupPaymentMint };
\ No newline at end of file